@@ -0,0 +1,53 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// File that mutating actions are appended to as JSON lines, set by `--audit-log`
+pub static AUDIT_LOG: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(serde::Serialize)]
+struct Entry<'a> {
+    timestamp: String,
+    actor: Option<String>,
+    action: &'a str,
+    repository: &'a str,
+    number: usize,
+    result: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Appends the outcome of `action` on the pullrequest to the audit log, if one is set.
+///
+/// Failing to write the log is reported but does not fail the action.
+pub async fn record<T>(action: &str, slug: &str, num: usize, res: &crate::error::Result<T>) {
+    let path = match AUDIT_LOG.get() {
+        Some(path) if !crate::config::dry_run() => path,
+        _ => return,
+    };
+    let timestamp = time::OffsetDateTime::now_utc()
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default();
+    let entry = Entry {
+        timestamp,
+        actor: crate::cmd::viewer::get().await.ok(),
+        action,
+        repository: slug,
+        number: num,
+        result: if res.is_ok() { "ok" } else { "error" },
+        error: res.as_ref().err().map(|e| e.to_string()),
+    };
+    if let Err(e) = append(path, &entry) {
+        eprintln!("failed to write audit log {}: {}", path.display(), e);
+    }
+}
+
+fn append(path: &std::path::Path, entry: &Entry) -> crate::error::Result<()> {
+    let line = serde_json::to_string(entry)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
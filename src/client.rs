@@ -0,0 +1,105 @@
+use crate::config::TOKEN;
+use isahc::config::Configurable;
+use once_cell::sync::Lazy;
+use std::convert::TryInto;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+pub const USER_AGENT: &str = concat!("gh-chk/", env!("CARGO_PKG_VERSION"));
+
+/// HTTP client shared by all requests, carrying the token, user agent, timeout and proxy settings
+pub static CLIENT: Lazy<surf::Client> = Lazy::new(|| {
+    config(Some(&TOKEN))
+        .try_into()
+        .expect("configure http client")
+});
+
+/// Client without the token, for requests that carry their own credentials
+pub static ANONYMOUS_CLIENT: Lazy<surf::Client> =
+    Lazy::new(|| config(None).try_into().expect("configure http client"));
+
+fn config(token: Option<&str>) -> surf::Config {
+    let mut builder = isahc::HttpClient::builder().timeout(TIMEOUT);
+    if let Some(proxy) = env_var(&["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"]) {
+        match proxy.parse::<isahc::http::Uri>() {
+            Ok(uri) => builder = builder.proxy(uri),
+            Err(e) => eprintln!("ignoring invalid proxy {}: {}", proxy, e),
+        }
+    }
+    if let Some(no_proxy) = env_var(&["NO_PROXY", "no_proxy"]) {
+        let hosts: Vec<String> = no_proxy
+            .split(',')
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+            .map(String::from)
+            .collect();
+        builder = builder.proxy_blacklist(hosts);
+    }
+    let client = builder.build().expect("build http client");
+    let config = surf::Config::new()
+        .set_http_client(http_client::isahc::IsahcClient::from_client(client))
+        .add_header("User-Agent", USER_AGENT)
+        .expect("set user agent header");
+    match token {
+        Some(token) => config
+            .add_header("Authorization", format!("bearer {}", token))
+            .expect("set authorization header"),
+        None => config,
+    }
+}
+
+/// Slots of in-flight requests; a full channel makes further senders wait
+static PERMITS: Lazy<(
+    async_std::channel::Sender<()>,
+    async_std::channel::Receiver<()>,
+)> = Lazy::new(|| async_std::channel::bounded(crate::config::concurrency()));
+
+/// Number of requests sent, for the `--timing` summary
+pub static SENT: AtomicUsize = AtomicUsize::new(0);
+
+/// Sends the request once fewer than `--concurrency` requests are in flight,
+/// reporting its duration under `label` with `--timing`
+pub async fn send(label: &str, req: surf::RequestBuilder) -> surf::Result<surf::Response> {
+    let (tx, rx) = &*PERMITS;
+    tx.send(()).await.expect("permit channel open");
+    let start = Instant::now();
+    let res = req.await;
+    let _ = rx.try_recv();
+    SENT.fetch_add(1, Ordering::Relaxed);
+    if crate::config::timing() {
+        eprintln!("timing: {:>8.3}s {}", start.elapsed().as_secs_f64(), label);
+    }
+    res
+}
+
+fn env_var(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_sends_user_agent() {
+        for config in [config(Some("secret")), config(None)] {
+            let ua = config
+                .headers
+                .get(&"User-Agent".into())
+                .expect("user agent");
+            assert_eq!(ua.as_str(), USER_AGENT);
+        }
+        assert!(USER_AGENT.starts_with("gh-chk/"));
+    }
+
+    #[test]
+    fn config_sends_token_only_when_given() {
+        let auth = config(Some("secret"));
+        let auth = auth.headers.get(&"Authorization".into());
+        assert_eq!(auth.map(|v| v.as_str()), Some("bearer secret"));
+        assert!(!config(None).headers.contains_key(&"Authorization".into()));
+    }
+}
@@ -1,7 +1,184 @@
+use std::collections::HashSet;
+
 pub mod contributions;
+pub mod diff;
 pub mod issues;
+pub mod issuesearch;
 pub mod notifications;
 pub mod prs;
+pub mod pullrequest;
+pub mod raw;
+pub mod releases;
+pub mod repo;
+pub mod reviewrequests;
+pub mod schema;
+pub mod scopes;
 pub mod search;
+pub mod starred;
 pub mod trackassignees;
 pub mod viewer;
+
+/// Repository filters for owner-wide queries
+#[derive(Debug, clap::Args)]
+pub struct RepoFilter {
+    /// Skip archived repositories
+    #[clap(long = "no-archived")]
+    no_archived: bool,
+    /// Skip forked repositories
+    #[clap(long = "no-forks")]
+    no_forks: bool,
+    /// Only include repositories tagged with this topic
+    #[clap(long = "topic")]
+    topic: Option<String>,
+}
+
+impl RepoFilter {
+    /// Variables for the `isArchived`/`isFork` arguments of `repositories`
+    pub fn variables(&self) -> serde_json::Value {
+        serde_json::json!({
+            "isArchived": self.no_archived.then_some(false),
+            "isFork": self.no_forks.then_some(false),
+            "withTopics": self.topic.is_some(),
+        })
+    }
+
+    /// Whether a repository with `topics`, selected when `withTopics` is set, passes `--topic`
+    pub fn keeps(&self, topics: Option<&RepositoryTopics>) -> bool {
+        match &self.topic {
+            Some(topic) => topics.is_some_and(|topics| {
+                topics
+                    .nodes
+                    .iter()
+                    .any(|node| node.topic.name.eq_ignore_ascii_case(topic))
+            }),
+            None => true,
+        }
+    }
+}
+
+/// `repositoryTopics` of a repository
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct RepositoryTopics {
+    nodes: Vec<TopicNode>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct TopicNode {
+    topic: Topic,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct Topic {
+    name: String,
+}
+
+/// Slugs of the arguments and the `--from-file` file, with `-` reading more from stdin.
+///
+/// Files and stdin hold one slug per line; blank lines and `#` comments are skipped.
+pub fn read_slugs(
+    args: &[String],
+    from_file: Option<&std::path::Path>,
+) -> crate::error::Result<Vec<String>> {
+    let mut slugs = Vec::new();
+    for arg in args {
+        if arg == "-" {
+            slugs.extend(parse_slugs(&std::io::read_to_string(std::io::stdin())?));
+        } else {
+            slugs.push(arg.clone());
+        }
+    }
+    if let Some(path) = from_file {
+        slugs.extend(parse_slugs(&std::fs::read_to_string(path)?));
+    }
+    Ok(slugs)
+}
+
+fn parse_slugs(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Pagination state of a GraphQL connection
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+impl PageInfo {
+    /// Cursor to pass as `after` for the following page, if there is one
+    pub fn next(&self) -> Option<&str> {
+        self.end_cursor.as_deref().filter(|_| self.has_next_page)
+    }
+}
+
+/// Prints the total of a listing command run with `--count`
+pub fn print_count(count: usize) -> crate::error::Result<()> {
+    match crate::config::FORMAT.get() {
+        Some(&crate::config::Format::Json) => {
+            outln!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({ "count": count }))?
+            )
+        }
+        _ => outln!("{count}"),
+    }
+    Ok(())
+}
+
+/// Fails with `Error::Empty` for a listing that found nothing under `--fail-on-empty`
+pub fn ensure_found(count: usize) -> crate::error::Result<()> {
+    match crate::config::FAIL_ON_EMPTY.get() {
+        Some(true) if count == 0 => Err(crate::error::Error::Empty),
+        _ => Ok(()),
+    }
+}
+
+/// Pretty JSON of a result, projected to the `--fields` keys if given
+pub fn to_json<T: serde::Serialize>(value: &T) -> crate::error::Result<String> {
+    let mut v = serde_json::to_value(value)?;
+    if let Some(fields) = crate::config::FIELDS.get() {
+        let mut seen = HashSet::new();
+        project(&mut v, fields, &mut seen);
+        for field in fields.iter().filter(|f| !seen.contains(*f)) {
+            eprintln!("unknown field: {}", field);
+        }
+    }
+    Ok(serde_json::to_string_pretty(&v)?)
+}
+
+/// Keeps only `fields` in the items, the objects in arrays that hold no nested list of objects
+fn project(v: &mut serde_json::Value, fields: &[String], seen: &mut HashSet<String>) {
+    match v {
+        serde_json::Value::Array(items) => {
+            for item in items {
+                match item {
+                    serde_json::Value::Object(obj) if !obj.values().any(has_objects) => {
+                        obj.retain(|k, _| fields.contains(k));
+                        seen.extend(obj.keys().cloned());
+                    }
+                    _ => project(item, fields, seen),
+                }
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            for child in obj.values_mut() {
+                project(child, fields, seen)
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether the value holds a list of objects; an empty list may be one that matched nothing
+fn has_objects(v: &serde_json::Value) -> bool {
+    match v {
+        serde_json::Value::Array(vs) => vs.is_empty() || vs.iter().any(|v| v.is_object()),
+        serde_json::Value::Object(obj) => obj.values().any(has_objects),
+        _ => false,
+    }
+}
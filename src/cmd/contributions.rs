@@ -15,6 +15,7 @@ nestruct::nest! {
                             contribution_days: [{
                                 color: String,
                                 contribution_count: usize,
+                                contribution_level: String,
                             }]
                         }]
                     }
@@ -24,35 +25,403 @@ nestruct::nest! {
     }
 }
 
-pub async fn check(user: Option<String>) -> surf::Result<()> {
-    let user = user.unwrap_or(crate::cmd::viewer::get().await?);
-    let var = json!({ "login": user });
-    let q = json!({ "query": include_str!("../query/contributions.graphql"), "variables": var });
-    let res = crate::graphql::query::<res::Res>(&q).await?;
+#[derive(Debug, clap::Parser)]
+pub struct Args {
+    user: Option<String>,
+    /// Redraw the calendar periodically until interrupted with Ctrl-C
+    #[clap(long = "watch")]
+    watch: bool,
+    /// Seconds between redraws in watch mode
+    #[clap(long = "interval", default_value = "300", value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+    /// Number of trailing weeks to show (default: as many as fit the terminal)
+    #[clap(long = "weeks")]
+    weeks: Option<usize>,
+    /// Color days by GitHub's five intensity levels on a fixed green ramp
+    #[clap(long = "buckets")]
+    buckets: bool,
+    /// Number of past years to show, one query per year
+    #[clap(long = "years", value_parser = clap::value_parser!(u32).range(1..))]
+    years: Option<u32>,
+    /// Print only today's count, this week's total and the current streak
+    #[clap(long = "summary", alias = "today", conflicts_with = "years")]
+    summary: bool,
+}
+
+#[derive(serde::Serialize)]
+struct Summary {
+    today: usize,
+    week: usize,
+    streak: usize,
+}
+
+/// Colors of the `NONE` to `FOURTH_QUARTILE` contribution levels
+const RAMP: [(u8, u8, u8); 5] = [
+    (0xeb, 0xed, 0xf0),
+    (0x9b, 0xe9, 0xa8),
+    (0x40, 0xc4, 0x63),
+    (0x30, 0xa1, 0x4e),
+    (0x21, 0x6e, 0x39),
+];
+
+/// Width of a week row with 3-char day cells, below which compact cells are used
+const FULL_ROW_WIDTH: usize = 52;
+
+pub async fn check(args: &Args) -> crate::error::Result<()> {
+    if !args.watch {
+        return show(args).await;
+    }
+    crate::output::no_pager()?;
+    loop {
+        out!("\x1b[2J\x1b[H");
+        show(args).await?;
+        crate::output::flush()?;
+        async_std::task::sleep(std::time::Duration::from_secs(args.interval)).await;
+    }
+}
+
+async fn show(args: &Args) -> crate::error::Result<()> {
+    let user = match &args.user {
+        Some(user) => user.clone(),
+        None => crate::cmd::viewer::get().await?,
+    };
+    let years = match args.years {
+        Some(years) => years,
+        None => {
+            let res = fetch(&user, json!(null), json!(null)).await?;
+            if args.summary {
+                return print_summary(&summarize(&res));
+            }
+            match crate::config::FORMAT.get() {
+                Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(&res)?),
+                _ => print_text(&res, args)?,
+            }
+            return Ok(());
+        }
+    };
+    let now = time::OffsetDateTime::now_utc();
+    let mut windows = Vec::new();
+    for k in (0..years as i64).rev() {
+        let to = now - time::Duration::days(365 * k);
+        // starts the day after the previous window ends, so no day is counted twice
+        let from = to - time::Duration::days(364);
+        windows.push((rfc3339(from)?, rfc3339(to)?));
+    }
+    let results = futures::future::join_all(
+        windows
+            .iter()
+            .map(|(from, to)| fetch(&user, json!(from), json!(to))),
+    )
+    .await
+    .into_iter()
+    .collect::<crate::error::Result<Vec<_>>>()?;
     match crate::config::FORMAT.get() {
-        Some(&crate::config::Format::Json) => println!("{}", serde_json::to_string_pretty(&res)?),
-        _ => print_text(&res)?,
+        Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(&merge(results))?),
+        _ => print_years(&windows, &results, args)?,
+    }
+    Ok(())
+}
+
+async fn fetch(
+    user: &str,
+    from: serde_json::Value,
+    to: serde_json::Value,
+) -> crate::error::Result<res::Res> {
+    let var = json!({ "login": user, "from": from, "to": to });
+    let q = json!({ "query": include_str!("../query/contributions.graphql"), "variables": var });
+    match crate::graphql::query::<res::Res>(&q).await {
+        Ok(res) => Ok(res),
+        Err(e) => Err(explain_owner(user).await.unwrap_or(e).into()),
+    }
+}
+
+fn rfc3339(t: time::OffsetDateTime) -> crate::error::Result<String> {
+    t.format(&time::format_description::well_known::Rfc3339)
+        .map_err(|e| crate::error::Error::Parse(e.to_string()))
+}
+
+/// Joins the calendars of consecutive windows into one, summing the totals
+fn merge(results: Vec<res::Res>) -> res::Res {
+    let mut results = results.into_iter();
+    let mut merged = results.next().expect("at least one year");
+    let calendar = &mut merged
+        .data
+        .user
+        .contributions_collection
+        .contribution_calendar;
+    for mut res in results {
+        let more = &mut res.data.user.contributions_collection.contribution_calendar;
+        calendar.total_contributions += more.total_contributions;
+        // a week split by the window boundary comes back from both windows
+        if let (Some(last), Some(first)) = (calendar.weeks.last_mut(), more.weeks.first_mut()) {
+            if last.first_day == first.first_day {
+                last.contribution_days.append(&mut first.contribution_days);
+                more.weeks.remove(0);
+            }
+        }
+        calendar.weeks.append(&mut more.weeks);
+    }
+    merged
+}
+
+#[derive(serde::Deserialize)]
+struct OwnerRes {
+    data: OwnerData,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OwnerData {
+    repository_owner: Option<Owner>,
+}
+
+#[derive(serde::Deserialize)]
+struct Owner {
+    #[serde(rename = "__typename")]
+    typename: String,
+}
+
+/// Turns a failed contributions lookup into a clearer error when the login is not a user
+async fn explain_owner(login: &str) -> Option<surf::Error> {
+    let v = json!({ "login": login });
+    let q = json!({ "query": include_str!("../query/owner.type.graphql"), "variables": v });
+    let res = crate::graphql::query::<OwnerRes>(&q).await.ok()?;
+    let msg = match res.data.repository_owner {
+        None => format!("{} was not found", login),
+        Some(owner) if owner.typename != "User" => format!(
+            "contributions are only available for users: {} is an {}",
+            login,
+            owner.typename.to_lowercase()
+        ),
+        Some(_) => return None,
+    };
+    Some(surf::Error::from_str(surf::StatusCode::NotFound, msg))
+}
+
+fn print_text(res: &res::Res, args: &Args) -> crate::error::Result<()> {
+    let calendar = &res.data.user.contributions_collection.contribution_calendar;
+    if calendar.weeks.is_empty() {
+        outln!("no contributions in range");
+        return Ok(());
     }
+    let size = crate::output::terminal_size();
+    let compact = size.is_some_and(|(width, _)| width < FULL_ROW_WIDTH);
+    let weeks = match (args.weeks, size) {
+        (Some(weeks), _) => weeks,
+        // keep room for the total line and the prompt
+        (None, Some((_, height))) => height.saturating_sub(2),
+        (None, None) => calendar.weeks.len(),
+    };
+    for week in calendar
+        .weeks
+        .iter()
+        .skip(calendar.weeks.len().saturating_sub(weeks))
+    {
+        print_week(week, compact, args)?;
+    }
+    outln!("total contributions: {}", calendar.total_contributions);
     Ok(())
 }
 
-fn print_text(res: &res::Res) -> surf::Result<()> {
+fn summarize(res: &res::Res) -> Summary {
     let calendar = &res.data.user.contributions_collection.contribution_calendar;
-    for week in &calendar.weeks {
-        print!("{}: ", week.first_day);
-        let mut week_count = 0f64;
-        for day in &week.contribution_days {
-            week_count += day.contribution_count as f64;
-            let r = u8::from_str_radix(day.color.get(1..3).unwrap_or_default(), 16)?;
-            let g = u8::from_str_radix(day.color.get(3..5).unwrap_or_default(), 16)?;
-            let b = u8::from_str_radix(day.color.get(5..7).unwrap_or_default(), 16)?;
+    let counts: Vec<usize> = calendar
+        .weeks
+        .iter()
+        .flat_map(|week| week.contribution_days.iter())
+        .map(|day| day.contribution_count)
+        .collect();
+    let today = counts.last().copied().unwrap_or_default();
+    let week = calendar.weeks.last().map_or(0, |week| {
+        week.contribution_days
+            .iter()
+            .map(|day| day.contribution_count)
+            .sum()
+    });
+    // a streak still counts while today has no contributions yet
+    let skip = usize::from(today == 0);
+    let streak = counts
+        .iter()
+        .rev()
+        .skip(skip)
+        .take_while(|&&count| count > 0)
+        .count();
+    Summary {
+        today,
+        week,
+        streak,
+    }
+}
+
+fn print_summary(summary: &Summary) -> crate::error::Result<()> {
+    match crate::config::FORMAT.get() {
+        Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(summary)?),
+        _ => outln!(
+            "today: {}  week: {}  streak: {}",
+            summary.today,
+            summary.week,
+            summary.streak
+        ),
+    }
+    Ok(())
+}
+
+/// Prints every week of the yearly windows, headed by the range of each window
+fn print_years(
+    windows: &[(String, String)],
+    results: &[res::Res],
+    args: &Args,
+) -> crate::error::Result<()> {
+    let compact = crate::output::terminal_size().is_some_and(|(width, _)| width < FULL_ROW_WIDTH);
+    let mut total = 0;
+    for ((from, to), res) in windows.iter().zip(results) {
+        let calendar = &res.data.user.contributions_collection.contribution_calendar;
+        outln!(
+            "{}",
+            format!(
+                "== {} .. {} ==",
+                from.get(..10).unwrap_or(from),
+                to.get(..10).unwrap_or(to)
+            )
+            .bold()
+        );
+        for week in &calendar.weeks {
+            print_week(week, compact, args)?;
+        }
+        total += calendar.total_contributions;
+    }
+    outln!("total contributions: {}", total);
+    Ok(())
+}
+
+fn print_week(
+    week: &res::data::user::contributions_collection::contribution_calendar::weeks::Weeks,
+    compact: bool,
+    args: &Args,
+) -> crate::error::Result<()> {
+    out!("{}: ", week.first_day);
+    let mut week_count = 0;
+    for day in &week.contribution_days {
+        week_count += day.contribution_count;
+        let (r, g, b) = if args.buckets {
+            RAMP[level(&day.contribution_level)]
+        } else {
+            hex_color(&day.color)?
+        };
+        if compact {
+            out!("{}", " ".on_truecolor(r, g, b))
+        } else {
             let cnt = format!("{:3}", day.contribution_count);
-            print!("{} ", cnt.as_str().color("black").on_truecolor(r, g, b))
+            out!("{} ", cnt.as_str().color("black").on_truecolor(r, g, b))
         }
-        let l = week.contribution_days.len() as f64;
-        print!("{} {:>5.2}", week_count, week_count / l);
-        println!();
     }
-    println!("total contributions: {}", calendar.total_contributions);
+    out!(
+        "{}{} {:>5.2}",
+        if compact { " " } else { "" },
+        week_count,
+        daily_average(week)
+    );
+    outln!();
     Ok(())
 }
+
+/// Contributions per day of the week, 0 for a week without days
+fn daily_average(
+    week: &res::data::user::contributions_collection::contribution_calendar::weeks::Weeks,
+) -> f64 {
+    let total: usize = week
+        .contribution_days
+        .iter()
+        .map(|day| day.contribution_count)
+        .sum();
+    total as f64 / week.contribution_days.len().max(1) as f64
+}
+
+fn level(contribution_level: &str) -> usize {
+    match contribution_level {
+        "FIRST_QUARTILE" => 1,
+        "SECOND_QUARTILE" => 2,
+        "THIRD_QUARTILE" => 3,
+        "FOURTH_QUARTILE" => 4,
+        _ => 0,
+    }
+}
+
+fn hex_color(color: &str) -> crate::error::Result<(u8, u8, u8)> {
+    let r = u8::from_str_radix(color.get(1..3).unwrap_or_default(), 16)?;
+    let g = u8::from_str_radix(color.get(3..5).unwrap_or_default(), 16)?;
+    let b = u8::from_str_radix(color.get(5..7).unwrap_or_default(), 16)?;
+    Ok((r, g, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn calendar(weeks: serde_json::Value) -> res::Res {
+        serde_json::from_value(json!({
+            "data": { "user": { "contributionsCollection": { "contributionCalendar": {
+                "totalContributions": 0,
+                "weeks": weeks
+            } } } }
+        }))
+        .expect("calendar fixture")
+    }
+
+    #[test]
+    fn empty_calendar_prints() {
+        let args = Args::parse_from(["contributions"]);
+        assert!(print_text(&calendar(json!([])), &args).is_ok());
+    }
+
+    #[test]
+    fn empty_calendar_summarizes_to_zero() {
+        let summary = summarize(&calendar(json!([])));
+        assert_eq!((summary.today, summary.week, summary.streak), (0, 0, 0));
+    }
+
+    #[test]
+    fn merge_joins_the_boundary_week() {
+        let day = |count: usize| json!({ "color": "#ebedf0", "contributionCount": count, "contributionLevel": "NONE" });
+        let first = calendar(json!([
+            { "firstDay": "2024-01-07", "contributionDays": [day(1)] },
+            { "firstDay": "2024-01-14", "contributionDays": [day(2), day(3)] }
+        ]));
+        let second = calendar(json!([
+            { "firstDay": "2024-01-14", "contributionDays": [day(4)] },
+            { "firstDay": "2024-01-21", "contributionDays": [day(5)] }
+        ]));
+        let merged = merge(vec![first, second]);
+        let weeks = &merged
+            .data
+            .user
+            .contributions_collection
+            .contribution_calendar
+            .weeks;
+        let days: Vec<(&str, usize)> = weeks
+            .iter()
+            .map(|w| (w.first_day.as_str(), w.contribution_days.len()))
+            .collect();
+        assert_eq!(
+            days,
+            [("2024-01-07", 1), ("2024-01-14", 3), ("2024-01-21", 1)]
+        );
+    }
+
+    #[test]
+    fn week_without_days() {
+        let res = calendar(json!([{ "firstDay": "2024-01-07", "contributionDays": [] }]));
+        let week = &res
+            .data
+            .user
+            .contributions_collection
+            .contribution_calendar
+            .weeks[0];
+        assert_eq!(daily_average(week), 0.0);
+        let summary = summarize(&res);
+        assert_eq!((summary.today, summary.week, summary.streak), (0, 0, 0));
+        let args = Args::parse_from(["contributions"]);
+        assert!(print_text(&res, &args).is_ok());
+    }
+}
@@ -0,0 +1,104 @@
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct File {
+    filename: String,
+    status: String,
+    additions: usize,
+    deletions: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    patch: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Stat<'a> {
+    filename: &'a str,
+    additions: usize,
+    deletions: usize,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Args {
+    slug: String,
+    num: usize,
+    /// Only show the number of changed lines per file
+    #[clap(long = "stat")]
+    stat: bool,
+    /// Emit the per-file line counts as JSON, without patches
+    #[clap(long = "stat-json", conflicts_with = "stat")]
+    stat_json: bool,
+}
+
+pub async fn show(args: &Args) -> crate::error::Result<()> {
+    let vs: Vec<&str> = args.slug.split('/').collect();
+    if vs.len() != 2 {
+        return Err(crate::error::Error::InvalidInput(format!(
+            "unknown slug format: {}",
+            args.slug
+        )));
+    }
+    let path = format!("repos/{}/{}/pulls/{}/files", vs[0], vs[1], args.num);
+    let q = crate::rest::QueryMap::new();
+    let mut files = Vec::new();
+    let mut page = 1;
+    loop {
+        let mut page_files = crate::rest::get::<File>(&path, page, &q).await?;
+        if page_files.is_empty() {
+            break;
+        }
+        files.append(&mut page_files);
+        page += 1;
+    }
+    if args.stat_json {
+        let stats: Vec<Stat> = files
+            .iter()
+            .map(|f| Stat {
+                filename: &f.filename,
+                additions: f.additions,
+                deletions: f.deletions,
+            })
+            .collect();
+        outln!("{}", crate::cmd::to_json(&stats)?);
+        return Ok(());
+    }
+    match crate::config::FORMAT.get() {
+        Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(&files)?),
+        _ => print_text(&files, args.stat),
+    }
+    Ok(())
+}
+
+fn print_text(files: &[File], stat: bool) {
+    let (mut additions, mut deletions) = (0, 0);
+    for f in files {
+        additions += f.additions;
+        deletions += f.deletions;
+        outln!(
+            "{:>6} {:>6} {} {}",
+            format!("+{}", f.additions).green(),
+            format!("-{}", f.deletions).red(),
+            f.filename.bold(),
+            f.status.dimmed()
+        );
+        if stat {
+            continue;
+        }
+        if let Some(patch) = &f.patch {
+            for line in patch.lines() {
+                match line.chars().next() {
+                    Some('+') => outln!("{}", line.green()),
+                    Some('-') => outln!("{}", line.red()),
+                    Some('@') => outln!("{}", line.cyan()),
+                    _ => outln!("{line}"),
+                }
+            }
+        }
+    }
+    outln!(
+        "{} files changed, {} insertions(+), {} deletions(-)",
+        files.len(),
+        additions,
+        deletions
+    );
+}
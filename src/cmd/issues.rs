@@ -2,7 +2,7 @@ use colored::Colorize;
 use serde_json::json;
 
 nestruct::nest! {
-    #[derive(serde::Serialize, serde::Deserialize)]
+    #[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
     #[serde(rename_all="camelCase")]
     Res {
         data: {
@@ -10,6 +10,8 @@ nestruct::nest! {
                 repositories: {
                     nodes: [{
                         name: String,
+                        #[serde(default, skip_serializing)]
+                        repository_topics: Option<crate::cmd::RepositoryTopics>,
                         issues: {
                             nodes: [{
                                 number: usize,
@@ -24,31 +26,72 @@ nestruct::nest! {
     }
 }
 
-pub async fn check(slugs: Vec<String>) -> surf::Result<()> {
-    let slugs = if slugs.is_empty() {
+/// JSON Schema of the `issues` output
+pub fn schema() -> schemars::Schema {
+    crate::cmd::schema::serialized::<res::Res>()
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Args {
+    /// Repository or owner slugs; `-` reads them from stdin, one per line
+    slug: Vec<String>,
+    /// Read more slugs from this file, one per line, with `#` comments
+    #[clap(long = "from-file")]
+    from_file: Option<std::path::PathBuf>,
+    #[clap(flatten)]
+    repo_filter: crate::cmd::RepoFilter,
+}
+
+pub async fn check(args: &Args) -> crate::error::Result<()> {
+    let slugs = if args.slug.is_empty() && args.from_file.is_none() {
         vec![crate::cmd::viewer::get().await?]
     } else {
-        slugs
+        crate::cmd::read_slugs(&args.slug, args.from_file.as_deref())?
     };
+    let mut count = 0usize;
     for slug in slugs {
         let vs: Vec<String> = slug.split('/').map(String::from).collect();
         match vs.len() {
-            1 => check_owner(&vs[0]).await?,
-            _ => panic!("unknown slug format"),
+            1 => count += check_owner(&vs[0], args).await?,
+            _ => {
+                return Err(crate::error::Error::InvalidInput(format!(
+                    "unknown slug format: {}",
+                    slug
+                )))
+            }
         }
     }
-    Ok(())
+    if crate::config::count_only() {
+        crate::cmd::print_count(count)?;
+    }
+    crate::cmd::ensure_found(count)
 }
 
-async fn check_owner(owner: &str) -> surf::Result<()> {
-    let v = json!({ "login": owner });
+/// Prints the issues of the owner and returns how many there were
+async fn check_owner(owner: &str, args: &Args) -> crate::error::Result<usize> {
+    let mut v = args.repo_filter.variables();
+    v["login"] = json!(owner);
     let q = json!({ "query": include_str!("../query/issues.graphql"), "variables": v });
-    let res = crate::graphql::query::<res::Res>(&q).await?;
+    let mut res = crate::graphql::query::<res::Res>(&q).await?;
+    res.data
+        .repository_owner
+        .repositories
+        .nodes
+        .retain(|repo| args.repo_filter.keeps(repo.repository_topics.as_ref()));
+    let count = res
+        .data
+        .repository_owner
+        .repositories
+        .nodes
+        .iter()
+        .map(|repo| repo.issues.nodes.len())
+        .sum();
     match crate::config::FORMAT.get() {
-        Some(&crate::config::Format::Json) => println!("{}", serde_json::to_string_pretty(&res)?),
+        _ if crate::config::count_only() => {}
+        Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(&res)?),
         _ => print_text(&res),
     }
-    Ok(())
+    Ok(count)
 }
 
 fn print_text(res: &res::Res) {
@@ -57,11 +100,16 @@ fn print_text(res: &res::Res) {
         if repo.issues.nodes.is_empty() {
             continue;
         }
-        println!("{}", repo.name.cyan());
+        outln!("{}", repo.name.cyan());
         for issue in &repo.issues.nodes {
             count += 1;
-            println!("  #{} {} {} ", issue.number, issue.url, issue.title)
+            outln!(
+                "  #{} {} {} ",
+                issue.number,
+                issue.url,
+                crate::output::hyperlink(&issue.title, &issue.url)
+            )
         }
     }
-    println!("Count of Issues: {count}");
+    outln!("Count of Issues: {count}");
 }
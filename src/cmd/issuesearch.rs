@@ -0,0 +1,90 @@
+use colored::Colorize;
+
+nestruct::nest! {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    Search {
+        total_count: usize,
+        incomplete_results: bool,
+        items: [{
+            number: usize,
+            title: String,
+            state: String,
+            html_url: String,
+            repository_url: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pull_request: Option<serde_json::Value>,
+        }]
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Filter {
+    Assigned,
+    Mentioned,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Args {
+    /// Include items in the given state
+    #[clap(long = "state", default_value = "open")]
+    state: State,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+enum State {
+    Open,
+    Closed,
+    All,
+}
+
+pub async fn search(filter: Filter, args: &Args) -> crate::error::Result<()> {
+    let login = crate::cmd::viewer::get().await?;
+    let mut q = match filter {
+        Filter::Assigned => format!("assignee:{}", login),
+        Filter::Mentioned => format!("mentions:{}", login),
+    };
+    match args.state {
+        State::Open => q += " is:open",
+        State::Closed => q += " is:closed",
+        State::All => {}
+    }
+    let query = crate::rest::QueryMap::from([("q".to_owned(), q)]);
+    if crate::config::dry_run() {
+        eprintln!(
+            "dry-run: GET {}search/issues query={:?}",
+            crate::config::api_root(),
+            query
+        );
+        return Ok(());
+    }
+    let search_result = crate::rest::get_object::<search::Search>("search/issues", &query).await?;
+    match crate::config::FORMAT.get() {
+        Some(&crate::config::Format::Json) => {
+            outln!("{}", crate::cmd::to_json(&search_result)?)
+        }
+        _ => print_text(&search_result),
+    }
+    Ok(())
+}
+
+fn print_text(res: &search::Search) {
+    let repos_root = crate::config::api_root() + "repos/";
+    for item in &res.items {
+        let repo = item.repository_url.trim_start_matches(repos_root.as_str());
+        let kind = if item.pull_request.is_some() {
+            "PR"
+        } else {
+            "Issue"
+        };
+        outln!(
+            "{} {:>6} {:5} {:6} {} {}",
+            repo.cyan(),
+            format!("#{}", item.number).bold(),
+            kind.yellow(),
+            item.state,
+            item.html_url,
+            crate::output::hyperlink(&item.title, &item.html_url)
+        );
+    }
+    outln!("# count: {}", res.items.len());
+}
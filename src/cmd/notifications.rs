@@ -1,11 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 nestruct::nest! {
-    #[derive(serde::Serialize, serde::Deserialize)]
+    #[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
     Notification {
         id: String,
         repository: {
@@ -18,62 +18,217 @@ nestruct::nest! {
             url: Option<String>,
         },
         reason: String,
-        #[serde(deserialize_with = "time::serde::iso8601::deserialize")]
+        #[serde(with = "time::serde::rfc3339")]
+        #[schemars(with = "String")]
         updated_at: time::OffsetDateTime,
     }
 }
 
-pub async fn list(read: bool) -> surf::Result<()> {
+/// JSON Schema of the `notifications` output
+pub fn schema() -> schemars::Schema {
+    crate::cmd::schema::serialized::<Vec<notification::Notification>>()
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Args {
+    /// Mark notifications of merged or closed items as read
+    #[clap(long = "read")]
+    read: bool,
+    /// Hide the notification id column
+    #[clap(long = "no-id")]
+    no_id: bool,
+    /// Only show notifications of the given subject type (e.g. PullRequest, Issue)
+    #[clap(long = "type")]
+    ntype: Option<String>,
+    /// Open the listed notifications in the browser
+    #[clap(long = "open")]
+    open: bool,
+    /// Maximum number of browser tabs to open
+    #[clap(long = "max-open", default_value = "10")]
+    max_open: usize,
+    /// Print counts grouped by reason and subject type instead of the list
+    #[clap(long = "summary")]
+    summary: bool,
+}
+
+#[derive(Serialize)]
+struct Summary<'a> {
+    reason: BTreeMap<&'a str, usize>,
+    #[serde(rename = "type")]
+    ntype: BTreeMap<&'a str, usize>,
+}
+
+pub async fn list(args: &Args) -> crate::error::Result<()> {
     let mut res = Vec::new();
     let mut page = 1;
-    while let Ok(mut page_res) = list_page(page).await {
+    loop {
+        let mut page_res = list_page(page).await?;
         if page_res.is_empty() {
             break;
         }
         res.append(&mut page_res);
         page += 1;
     }
+    if let Some(ntype) = &args.ntype {
+        res.retain(|n| n.subject.ntype.eq_ignore_ascii_case(ntype));
+    }
+    match crate::config::FORMAT.get() {
+        _ if crate::config::count_only() => crate::cmd::print_count(res.len())?,
+        _ if args.summary => print_summary(&res)?,
+        Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(&res)?),
+        _ => print_text(&res, args).await,
+    }
+    if args.open {
+        open_in_browser(&res, args.max_open);
+    }
+    crate::cmd::ensure_found(res.len())
+}
+
+fn print_summary(res: &[notification::Notification]) -> crate::error::Result<()> {
+    let mut summary = Summary {
+        reason: BTreeMap::new(),
+        ntype: BTreeMap::new(),
+    };
+    for n in res {
+        *summary.reason.entry(n.reason.as_str()).or_default() += 1;
+        *summary.ntype.entry(n.subject.ntype.as_str()).or_default() += 1;
+    }
     match crate::config::FORMAT.get() {
-        Some(&crate::config::Format::Json) => println!("{}", serde_json::to_string_pretty(&res)?),
-        _ => print_text(&res, read).await,
+        Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(&summary)?),
+        _ => {
+            let join = |counts: &BTreeMap<&str, usize>| {
+                counts
+                    .iter()
+                    .map(|(k, n)| format!("{}: {}", k, n))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            outln!("{} {}", "reason".magenta(), join(&summary.reason));
+            outln!("{} {}", "type".yellow(), join(&summary.ntype));
+            outln!("# count: {}", res.len());
+        }
     }
     Ok(())
 }
 
-pub async fn list_page(page: usize) -> surf::Result<Vec<notification::Notification>> {
+fn open_in_browser(res: &[notification::Notification], max_open: usize) {
+    let urls: Vec<String> = res
+        .iter()
+        .filter_map(|n| n.subject.url.as_deref())
+        .map(web_url)
+        .collect();
+    if urls.len() > max_open {
+        eprintln!(
+            "{}",
+            format!(
+                "opening only the first {} of {} notifications",
+                max_open,
+                urls.len()
+            )
+            .yellow()
+        );
+    }
+    for url in urls.iter().take(max_open) {
+        if let Err(e) = open::that(url) {
+            eprintln!("failed to open {}: {}", url, e);
+        }
+    }
+}
+
+/// Converts an API url of an issue or pullrequest to its web url
+fn web_url(api_url: &str) -> String {
+    let repos_root = crate::config::api_root() + "repos/";
+    let web_root = format!("https://{}/", crate::config::host());
+    api_url
+        .replacen(&repos_root, &web_root, 1)
+        .replace("/pulls/", "/pull/")
+}
+
+pub async fn list_page(page: usize) -> crate::error::Result<Vec<notification::Notification>> {
     let q = HashMap::new();
     let res = crate::rest::get::<notification::Notification>("notifications", page, &q).await?;
     Ok(res)
 }
 
-async fn print_text(res: &[notification::Notification], read: bool) {
-    for n in res {
-        let status = match &n.subject.url {
+async fn print_text(res: &[notification::Notification], args: &Args) {
+    let statuses = futures::future::join_all(res.iter().map(|n| async move {
+        match &n.subject.url {
             Some(url) => get_status(url).await.unwrap_or_default(),
             None => String::default(),
-        };
-        println!(
-            "{:10} {:12} {:11} {:6} {} {} {} {}",
-            n.id.black(),
-            n.reason.magenta(),
-            n.subject.ntype.yellow(),
-            status,
-            n.updated_at.date(),
-            n.repository.full_name.cyan(),
-            n.subject.title,
-            n.subject.url.clone().unwrap_or_default().green(),
+        }
+    }))
+    .await;
+    let id_w = width(res.iter().map(|n| n.id.as_str()), 12);
+    let reason_w = width(res.iter().map(|n| n.reason.as_str()), 20);
+    let type_w = width(res.iter().map(|n| n.subject.ntype.as_str()), 12);
+    let status_w = width(statuses.iter().map(String::as_str), 6);
+    let repo_w = width(res.iter().map(|n| n.repository.full_name.as_str()), 40);
+    let url_w = width(res.iter().filter_map(|n| n.subject.url.as_deref()), 80);
+    for (n, status) in res.iter().zip(statuses) {
+        if !args.no_id {
+            out!("{} ", pad(&n.id, id_w).black());
+        }
+        outln!(
+            "{} {} {} {} {} {} {}",
+            pad(&n.reason, reason_w).magenta(),
+            pad(&n.subject.ntype, type_w).yellow(),
+            colorize_status(&pad(&status, status_w), &status),
+            colorize_age(n.updated_at),
+            pad(&n.repository.full_name, repo_w).cyan(),
+            pad(n.subject.url.as_deref().unwrap_or_default(), url_w).green(),
+            match n.subject.url.as_deref() {
+                Some(url) => crate::output::hyperlink(&n.subject.title, &web_url(url)),
+                None => n.subject.title.clone(),
+            },
         );
-        if read {
+        if args.read {
             match status.as_str() {
                 "MERGED" | "CLOSED" => {
                     let path = "notifications/threads/".to_owned() + &n.id;
-                    let _ = crate::rest::patch(&path).await;
+                    if let Err(e) = crate::rest::patch(&path).await {
+                        eprintln!("{}", e);
+                    }
                 }
                 _ => {}
             }
         }
     }
-    println!("# count: {}", res.len());
+    outln!("# count: {}", res.len());
+}
+
+/// Display width of the widest value, capped so one long value does not stretch the table
+fn width<'a>(values: impl Iterator<Item = &'a str>, cap: usize) -> usize {
+    values
+        .map(|v| v.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(cap)
+}
+
+fn pad(s: &str, width: usize) -> String {
+    format!("{:width$}", s)
+}
+
+fn colorize_status(s: &str, status: &str) -> colored::ColoredString {
+    match status {
+        "OPEN" => s.green(),
+        "MERGED" => s.magenta(),
+        "CLOSED" => s.red(),
+        _ => s.normal(),
+    }
+}
+
+fn colorize_age(updated_at: time::OffsetDateTime) -> colored::ColoredString {
+    let now = time::OffsetDateTime::now_utc().to_offset(crate::config::offset());
+    let updated_at = updated_at.to_offset(crate::config::offset());
+    let date = updated_at.date().to_string();
+    if updated_at.date() == now.date() {
+        date.red()
+    } else if now - updated_at < time::Duration::weeks(1) {
+        date.yellow()
+    } else {
+        date.dimmed()
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -93,11 +248,8 @@ enum Resource {
     PullRequestStatus { pr_state: String },
 }
 
-async fn get_status(api_url: &str) -> surf::Result<String> {
-    let url = api_url
-        .replace("api.github.com/repos", "github.com")
-        .replace("/pulls/", "/pull/");
-    let v = json!({ "url": url });
+async fn get_status(api_url: &str) -> crate::error::Result<String> {
+    let v = json!({ "url": web_url(api_url) });
     let q = json!({ "query": include_str!("../query/resource.status.graphql"), "variables": v });
     let res = crate::graphql::query::<Res>(&q).await?;
     Ok(match res.data.resource {
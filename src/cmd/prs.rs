@@ -1,30 +1,38 @@
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
 nestruct::nest! {
-    #[derive(serde::Serialize, serde::Deserialize, Debug)]
+    #[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema, Debug)]
     #[serde(rename_all = "camelCase")]
     Repository {
         name: String,
+        #[serde(default, skip_serializing)]
+        repository_topics: Option<crate::cmd::RepositoryTopics>,
         pull_requests: {
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            page_info: Option<crate::cmd::PageInfo>,
             nodes: [{
                 number: usize,
                 title: String,
                 url: String,
-                merge_state_status:
-                    #[nestruct(reset)]
-                    #[derive(serde::Serialize, serde::Deserialize, Debug)]
-                    #[serde(rename_all = "SCREAMING_SNAKE_CASE")] {
-                    Behind,
-                    Blocked,
-                    Clean,
-                    Dirty,
-                    Draft,
-                    HasHooks,
-                    Unknown,
-                    Unstable,
+                created_at: String,
+                is_draft: bool,
+                author: Option<crate::cmd::prs::Actor>,
+                head_ref_name: String,
+                base_ref_name: String,
+                is_cross_repository: bool,
+                head_repository_owner: Option<crate::cmd::prs::Actor>,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                merge_state_status: Option<crate::cmd::prs::MergeStateStatus>,
+                review_decision: Option<crate::cmd::prs::ReviewDecision>,
+                comments: {
+                    total_count: usize,
+                },
+                review_threads: {
+                    total_count: usize,
                 },
             }]
         }
@@ -32,12 +40,13 @@ nestruct::nest! {
 }
 
 nestruct::nest! {
-    #[derive(serde::Serialize, serde::Deserialize, Debug)]
+    #[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema, Debug)]
     #[serde(rename_all = "camelCase")]
     Res {
         data: {
             repository_owner: {
                 repositories: {
+                    page_info: crate::cmd::PageInfo,
                     nodes: [ crate::cmd::prs::repository::Repository ]
                 }
             }
@@ -46,7 +55,7 @@ nestruct::nest! {
 }
 
 nestruct::nest! {
-    #[derive(serde::Serialize, serde::Deserialize, Debug)]
+    #[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema, Debug)]
     #[serde(rename_all = "camelCase")]
     RepoRes {
         data: {
@@ -57,22 +66,93 @@ nestruct::nest! {
     }
 }
 
-impl Display for repository::pull_requests::nodes::Nodes {
+pub type PullRequest = repository::pull_requests::nodes::Nodes;
+
+/// JSON output of `prs`, by owner or by repository
+#[derive(schemars::JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum Output {
+    Owner(res::Res),
+    Repo(repo_res::RepoRes),
+}
+
+/// JSON Schema of the `prs` output
+pub fn schema() -> schemars::Schema {
+    crate::cmd::schema::serialized::<Output>()
+}
+
+/// Fields of `PullRequest`, to be appended to queries spreading `...PullRequestFields`
+pub const PULL_REQUEST_FRAGMENT: &str = include_str!("../query/pullrequest.fragment.graphql");
+
+/// With the alternate flag (`{:#}`), PRs without a review decision are marked `[-]`
+impl Display for PullRequest {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s = format!(
-            "{:>6} {} {} {}",
-            format!("#{}", self.number).bold(),
-            self.merge_state_status.to_emoji(),
-            self.url,
-            self.title.bold()
-        );
-        write!(f, "{}", self.merge_state_status.colorize(&s))
+        write!(f, "{}", self.render(DEFAULT_COLUMNS, f.alternate()))
+    }
+}
+
+/// Fields of a PR line in text output
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Column {
+    Number,
+    State,
+    Review,
+    Url,
+    Title,
+    Author,
+    Date,
+    Comments,
+    Branch,
+}
+
+/// Name of the column as given to `--columns`
+impl Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = clap::ValueEnum::to_possible_value(self).expect("no skipped columns");
+        f.write_str(value.get_name())
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// User or organization referenced by a PR, null once the account or fork is gone
+#[derive(Serialize, Deserialize, schemars::JsonSchema, Debug)]
+pub struct Actor {
+    login: String,
+}
+
+/// Logins of common bots, which GraphQL reports without the `[bot]` suffix
+const BOTS: &[&str] = &[
+    "dependabot",
+    "dependabot-preview",
+    "renovate",
+    "github-actions",
+    "greenkeeper",
+    "snyk-bot",
+    "imgbot",
+    "pre-commit-ci",
+    "allcontributors",
+];
+
+impl Actor {
+    fn is_bot(&self) -> bool {
+        let login = self.login.to_lowercase();
+        login.ends_with("[bot]") || BOTS.contains(&login.as_str())
+    }
+}
+
+const DEFAULT_COLUMNS: &[Column] = &[
+    Column::Number,
+    Column::State,
+    Column::Review,
+    Column::Url,
+    Column::Title,
+    Column::Author,
+    Column::Comments,
+];
+
+#[derive(Serialize, Deserialize, schemars::JsonSchema, Debug)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-enum MergeStateStatus {
+pub enum MergeStateStatus {
     Behind,
     Blocked,
     Clean,
@@ -83,7 +163,147 @@ enum MergeStateStatus {
     Unstable,
 }
 
-impl repository::pull_requests::nodes::merge_state_status::MergeStateStatus {
+#[derive(Serialize, Deserialize, schemars::JsonSchema, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+impl ReviewDecision {
+    const ALL: [Self; 3] = [Self::Approved, Self::ChangesRequested, Self::ReviewRequired];
+
+    fn to_tag(&self) -> &'static str {
+        match self {
+            Self::Approved => "[+]",
+            Self::ChangesRequested => "[!]",
+            Self::ReviewRequired => "[?]",
+        }
+    }
+
+    fn meaning(&self) -> &'static str {
+        match self {
+            Self::Approved => "approved",
+            Self::ChangesRequested => "changes requested",
+            Self::ReviewRequired => "review required",
+        }
+    }
+}
+
+/// Marker of PRs without a review decision, shown with `--show-no-review`
+const NO_REVIEW_TAG: &str = "[-]";
+
+impl PullRequest {
+    /// One-line summary made of `columns`, colored by the merge state
+    fn render(&self, columns: &[Column], show_no_review: bool) -> String {
+        let cells: Vec<String> = columns
+            .iter()
+            .filter_map(|column| match column {
+                Column::Number => Some(format!("{:>6}", format!("#{}", self.number).bold())),
+                Column::State => self.merge_state_status.as_ref().map(|s| s.indicator()),
+                Column::Review => self.review_status(show_no_review).map(String::from),
+                Column::Url => Some(self.url.clone()),
+                Column::Title => Some(crate::output::hyperlink(
+                    &self.title.bold().to_string(),
+                    &self.url,
+                )),
+                Column::Author => self.author.as_ref().map(|a| format!("@{}", a.login)),
+                Column::Date => self
+                    .created()
+                    .map(|c| c.to_offset(crate::config::offset()).date().to_string()),
+                Column::Branch => Some(self.branches()),
+                Column::Comments => {
+                    let discussions = self.comments.total_count + self.review_threads.total_count;
+                    (discussions > 0).then(|| {
+                        if crate::config::emoji() {
+                            format!("💬{discussions}")
+                        } else {
+                            format!("[{discussions} comments]")
+                        }
+                    })
+                }
+            })
+            .collect();
+        let s = cells.join(" ");
+        match &self.merge_state_status {
+            Some(status) => status.colorize(&s),
+            None => s,
+        }
+    }
+
+    /// `head → base`, with the head owner prefixed for PRs from forks
+    fn branches(&self) -> String {
+        let head = match &self.head_repository_owner {
+            Some(owner) if self.is_cross_repository => {
+                format!("{}:{}", owner.login, self.head_ref_name)
+            }
+            _ => self.head_ref_name.clone(),
+        };
+        format!("{} → {}", head, self.base_ref_name)
+    }
+
+    /// Git commands fetching and checking out the head branch; forks are fetched from
+    /// the PR ref into `pr-<number>`, since their branch name may clash with a local one
+    fn checkout_commands(&self) -> String {
+        if self.is_cross_repository {
+            format!(
+                "git fetch origin pull/{0}/head:pr-{0} && git checkout pr-{0}",
+                self.number
+            )
+        } else {
+            format!(
+                "git fetch origin {0} && git checkout {0}",
+                self.head_ref_name
+            )
+        }
+    }
+
+    /// Label of the review decision; `[-]` for none if `show_no_review`, otherwise nothing
+    fn review_status(&self, show_no_review: bool) -> Option<&'static str> {
+        match &self.review_decision {
+            Some(decision) => Some(decision.to_tag()),
+            None if show_no_review => Some(NO_REVIEW_TAG),
+            None => None,
+        }
+    }
+
+    fn created(&self) -> Option<time::OffsetDateTime> {
+        let format = time::format_description::well_known::Iso8601::DEFAULT;
+        time::OffsetDateTime::parse(&self.created_at, &format).ok()
+    }
+
+    fn is_stale(&self, days: i64) -> bool {
+        self.created()
+            .is_some_and(|c| time::OffsetDateTime::now_utc() - c > time::Duration::days(days))
+    }
+}
+
+impl MergeStateStatus {
+    const ALL: [Self; 8] = [
+        Self::Behind,
+        Self::Blocked,
+        Self::Clean,
+        Self::Dirty,
+        Self::Draft,
+        Self::HasHooks,
+        Self::Unknown,
+        Self::Unstable,
+    ];
+
+    fn meaning(&self) -> &'static str {
+        match self {
+            Self::Behind => "head branch is out of date with the base",
+            Self::Blocked => "merging is blocked, e.g. by missing approvals",
+            Self::Clean => "mergeable with passing checks",
+            Self::Dirty => "merge conflicts must be resolved",
+            Self::Draft => "draft, not ready for review",
+            Self::HasHooks => "mergeable with passing checks and pre-receive hooks",
+            Self::Unknown => "merge state is not computed yet",
+            Self::Unstable => "mergeable with failing or pending checks",
+        }
+    }
+
     fn to_emoji(&self) -> String {
         match self {
             Self::Behind => "⏩",
@@ -98,6 +318,29 @@ impl repository::pull_requests::nodes::merge_state_status::MergeStateStatus {
         .to_owned()
     }
 
+    fn to_tag(&self) -> String {
+        match self {
+            Self::Behind => "[behind]",
+            Self::Blocked => "[blocked]",
+            Self::Clean => "[clean]",
+            Self::Dirty => "[dirty]",
+            Self::Draft => "[draft]",
+            Self::HasHooks => "[hooks]",
+            Self::Unknown => "[unknown]",
+            Self::Unstable => "[unstable]",
+        }
+        .to_owned()
+    }
+
+    /// Emoji of the state, or an ASCII tag under `--no-emoji`
+    fn indicator(&self) -> String {
+        if crate::config::emoji() {
+            self.to_emoji()
+        } else {
+            self.to_tag()
+        }
+    }
+
     fn colorize(&self, s: &str) -> String {
         match self {
             Self::Behind => s.yellow(),
@@ -113,66 +356,594 @@ impl repository::pull_requests::nodes::merge_state_status::MergeStateStatus {
     }
 }
 
-pub async fn check(slugs: Vec<String>) -> surf::Result<()> {
-    let slugs = if slugs.is_empty() {
+#[derive(Debug, clap::Parser)]
+pub struct Args {
+    /// Repository or owner slugs; `-` reads them from stdin, one per line
+    slug: Vec<String>,
+    /// Read more slugs from this file, one per line, with `#` comments
+    #[clap(long = "from-file")]
+    from_file: Option<std::path::PathBuf>,
+    /// Report failed slugs and keep going instead of aborting
+    #[clap(long = "continue-on-error")]
+    continue_on_error: bool,
+    #[clap(flatten)]
+    repo_filter: crate::cmd::RepoFilter,
+    /// For owner slugs, only query these comma-separated repositories, one query each
+    #[clap(long = "repos", value_delimiter = ',')]
+    repos: Vec<String>,
+    /// Skip merge state and review decision lookups for a quicker listing
+    #[clap(long = "fast", alias = "no-merge-info")]
+    fast: bool,
+    /// Whether to include, exclude or only show draft PRs
+    #[clap(long = "drafts", default_value = "include")]
+    drafts: Drafts,
+    /// Comma-separated fields to show for each PR, in order
+    #[clap(long = "columns", value_delimiter = ',', default_values_t = DEFAULT_COLUMNS.to_vec())]
+    columns: Vec<Column>,
+    /// Print counts of PRs by merge state and review decision instead of the list
+    #[clap(long = "summary", alias = "count-by-state", conflicts_with = "fast")]
+    summary: bool,
+    /// Print git commands to check out each PR below it
+    #[clap(long = "print-checkout")]
+    print_checkout: bool,
+    /// Mark PRs without a review decision with [-]
+    #[clap(long = "show-no-review")]
+    show_no_review: bool,
+    /// Flag PRs open for longer than this many days with ⏳ ([stale] without emoji)
+    #[clap(long = "stale-days")]
+    stale_days: Option<i64>,
+    /// Follow pagination to fetch every page instead of only the first 100
+    #[clap(long = "all")]
+    all: bool,
+    /// Only show PRs with this review decision
+    #[clap(long = "review", conflicts_with = "fast")]
+    review: Option<ReviewFilter>,
+    /// Hide PRs opened by bots such as Dependabot or Renovate
+    #[clap(long = "exclude-bots", conflicts_with = "only_bots")]
+    exclude_bots: bool,
+    /// Only show PRs opened by bots
+    #[clap(long = "only-bots")]
+    only_bots: bool,
+    /// Only show PRs by these authors (case-insensitive, repeatable)
+    #[clap(long = "author")]
+    author: Vec<String>,
+    /// Redraw the list periodically until interrupted with Ctrl-C
+    #[clap(long = "watch")]
+    watch: bool,
+    /// Seconds between redraws in watch mode
+    #[clap(long = "interval", default_value = "60", value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+    /// Only show PRs created on or after this date (YYYY-MM-DD)
+    #[clap(long = "since", value_parser = parse_date)]
+    since: Option<time::Date>,
+    /// Only show PRs created on or before this date (YYYY-MM-DD)
+    #[clap(long = "until", value_parser = parse_date)]
+    until: Option<time::Date>,
+    /// Sort PRs by the given key
+    #[clap(long = "sort")]
+    sort: Option<SortKey>,
+    /// Order of the sorted PRs
+    #[clap(long = "order", default_value = "asc")]
+    order: Order,
+}
+
+fn parse_date(s: &str) -> Result<time::Date, String> {
+    time::Date::parse(s, &time::format_description::well_known::Iso8601::DATE)
+        .map_err(|e| format!("invalid date {:?}: {}", s, e))
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+enum Drafts {
+    Include,
+    Exclude,
+    Only,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+enum ReviewFilter {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+    /// No review decision, e.g. when the base branch requires no reviews
+    None,
+}
+
+impl ReviewFilter {
+    fn matches(&self, decision: Option<&ReviewDecision>) -> bool {
+        matches!(
+            (self, decision),
+            (Self::Approved, Some(ReviewDecision::Approved))
+                | (
+                    Self::ChangesRequested,
+                    Some(ReviewDecision::ChangesRequested)
+                )
+                | (Self::ReviewRequired, Some(ReviewDecision::ReviewRequired))
+                | (Self::None, None)
+        )
+    }
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+enum SortKey {
+    Number,
+    Created,
+    Title,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+enum Order {
+    Asc,
+    Desc,
+}
+
+enum Fetched {
+    Owner(res::Res),
+    Repo(repo_res::RepoRes),
+}
+
+impl Fetched {
+    fn pull_requests_mut(&mut self) -> Vec<&mut Vec<PullRequest>> {
+        match self {
+            Fetched::Owner(res) => res
+                .data
+                .repository_owner
+                .repositories
+                .nodes
+                .iter_mut()
+                .map(|repo| &mut repo.pull_requests.nodes)
+                .collect(),
+            Fetched::Repo(res) => {
+                vec![&mut res.data.repository_owner.repository.pull_requests.nodes]
+            }
+        }
+    }
+}
+
+fn arrange(fetched: &mut Fetched, args: &Args) {
+    for prs in fetched.pull_requests_mut() {
+        prs.retain(|pr| matches(pr, args));
+        sort(prs, args);
+    }
+}
+
+fn matches(pr: &PullRequest, args: &Args) -> bool {
+    let drafts = match args.drafts {
+        Drafts::Include => true,
+        Drafts::Exclude => !pr.is_draft,
+        Drafts::Only => pr.is_draft,
+    };
+    let author = args.author.is_empty()
+        || pr.author.as_ref().is_some_and(|author| {
+            args.author
+                .iter()
+                .any(|login| login.eq_ignore_ascii_case(&author.login))
+        });
+    let is_bot = pr.author.as_ref().is_some_and(Actor::is_bot);
+    let bots = !(args.exclude_bots && is_bot || args.only_bots && !is_bot);
+    let review = args
+        .review
+        .as_ref()
+        .is_none_or(|r| r.matches(pr.review_decision.as_ref()));
+    let created = pr
+        .created()
+        .map(|c| c.to_offset(crate::config::offset()).date());
+    let window = match (created, args.since, args.until) {
+        (None, None, None) => true,
+        (None, _, _) => false,
+        (Some(c), since, until) => since.is_none_or(|s| s <= c) && until.is_none_or(|u| c <= u),
+    };
+    drafts && author && bots && review && window
+}
+
+fn sort(prs: &mut [PullRequest], args: &Args) {
+    match args.sort {
+        Some(SortKey::Number) => prs.sort_by_key(|pr| pr.number),
+        Some(SortKey::Created) => prs.sort_by_key(|pr| pr.created()),
+        Some(SortKey::Title) => prs.sort_by_key(|pr| pr.title.to_lowercase()),
+        None => return,
+    }
+    if let Order::Desc = args.order {
+        prs.reverse();
+    }
+}
+
+pub async fn check(args: &Args) -> crate::error::Result<()> {
+    if let (Some(since), Some(until)) = (args.since, args.until) {
+        if since > until {
+            return Err(crate::error::Error::InvalidInput(format!(
+                "--since {} is after --until {}",
+                since, until
+            )));
+        }
+    }
+    // read once, as stdin cannot be read again on later redraws
+    let slugs = if args.slug.is_empty() && args.from_file.is_none() {
         vec![crate::cmd::viewer::get().await?]
     } else {
-        slugs
+        crate::cmd::read_slugs(&args.slug, args.from_file.as_deref())?
     };
-    for slug in slugs {
-        println!("{}", slug.bright_blue());
-        let vs: Vec<String> = slug.split('/').map(String::from).collect();
-        match vs.len() {
-            1 => check_owner(&vs[0]).await?,
-            2 => check_repo(&vs[0], &vs[1]).await?,
-            _ => panic!("unknown slug format"),
+    if !args.watch {
+        return show(&slugs, args).await;
+    }
+    crate::output::no_pager()?;
+    loop {
+        out!("\x1b[2J\x1b[H");
+        show(&slugs, args).await?;
+        crate::output::flush()?;
+        async_std::task::sleep(std::time::Duration::from_secs(args.interval)).await;
+    }
+}
+
+async fn show(slugs: &[String], args: &Args) -> crate::error::Result<()> {
+    let results = futures::future::join_all(slugs.iter().map(|slug| fetch(slug, args))).await;
+    let mut count = 0usize;
+    for (slug, res) in slugs.iter().zip(results) {
+        let json = matches!(
+            crate::config::FORMAT.get(),
+            Some(&crate::config::Format::Json)
+        );
+        // each JSON document stands alone, matching `schema prs`
+        if !crate::config::count_only() && !json {
+            outln!("{}", slug.bright_blue());
+        }
+        match res {
+            Ok(mut fetched) => {
+                arrange(&mut fetched, args);
+                count += fetched
+                    .pull_requests_mut()
+                    .iter()
+                    .map(|prs| prs.len())
+                    .sum::<usize>();
+                if !crate::config::count_only() {
+                    if args.summary {
+                        print_summary(&mut fetched)?
+                    } else {
+                        print(&fetched, args)?
+                    }
+                }
+            }
+            Err(e) if args.continue_on_error => eprintln!("{}: {}", slug.red(), e),
+            Err(e) => return Err(e),
+        }
+    }
+    if crate::config::count_only() {
+        crate::cmd::print_count(count)?;
+    }
+    crate::cmd::ensure_found(count)
+}
+
+async fn fetch(slug: &str, args: &Args) -> crate::error::Result<Fetched> {
+    let vs: Vec<String> = slug.split('/').map(String::from).collect();
+    match vs.len() {
+        1 if !args.repos.is_empty() => fetch_named_repos(&vs[0], args).await,
+        1 => {
+            let mut res = fetch_owner(&vs[0], args, None).await?;
+            let repos = &mut res.data.repository_owner.repositories;
+            while let (true, Some(cursor)) = (args.all, repos.page_info.next()) {
+                let mut more = fetch_owner(&vs[0], args, Some(cursor)).await?;
+                let more_repos = &mut more.data.repository_owner.repositories;
+                repos.nodes.append(&mut more_repos.nodes);
+                repos.page_info = std::mem::take(&mut more_repos.page_info);
+            }
+            repos
+                .nodes
+                .retain(|repo| args.repo_filter.keeps(repo.repository_topics.as_ref()));
+            futures::future::try_join_all(
+                repos
+                    .nodes
+                    .iter_mut()
+                    .map(|repo| fetch_more_prs(&vs[0], repo, args)),
+            )
+            .await?;
+            Ok(Fetched::Owner(res))
+        }
+        2 => Ok(Fetched::Repo(fetch_repo_pages(&vs[0], &vs[1], args).await?)),
+        _ => Err(crate::error::Error::InvalidInput(format!(
+            "unknown slug format: {}",
+            slug
+        ))),
+    }
+}
+
+/// Fetches the `--repos` of the owner one by one, in place of the owner-wide query
+async fn fetch_named_repos(owner: &str, args: &Args) -> crate::error::Result<Fetched> {
+    let results = futures::future::join_all(
+        args.repos
+            .iter()
+            .map(|name| fetch_repo_pages(owner, name, args)),
+    )
+    .await;
+    let mut nodes = Vec::new();
+    for res in results {
+        nodes.push(res?.data.repository_owner.repository);
+    }
+    Ok(Fetched::Owner(res::Res {
+        data: res::data::Data {
+            repository_owner: res::data::repository_owner::RepositoryOwner {
+                repositories: res::data::repository_owner::repositories::Repositories {
+                    page_info: crate::cmd::PageInfo::default(),
+                    nodes,
+                },
+            },
+        },
+    }))
+}
+
+/// Fetches the PRs of the repository, following pagination with `--all`
+async fn fetch_repo_pages(
+    owner: &str,
+    name: &str,
+    args: &Args,
+) -> crate::error::Result<repo_res::RepoRes> {
+    let mut res = fetch_repo(owner, name, args, None).await?;
+    fetch_more_prs(owner, &mut res.data.repository_owner.repository, args).await?;
+    Ok(res)
+}
+
+/// Appends the PRs past the first page of the repository with `--all`
+async fn fetch_more_prs(
+    owner: &str,
+    repo: &mut repository::Repository,
+    args: &Args,
+) -> crate::error::Result<()> {
+    let prs = &mut repo.pull_requests;
+    while let (true, Some(cursor)) = (args.all, prs.page_info.as_ref().and_then(|p| p.next())) {
+        let mut more = fetch_repo(owner, &repo.name, args, Some(cursor)).await?;
+        let more_prs = &mut more.data.repository_owner.repository.pull_requests;
+        prs.nodes.append(&mut more_prs.nodes);
+        prs.page_info = more_prs.page_info.take();
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct LegendEntry {
+    kind: &'static str,
+    value: serde_json::Value,
+    symbol: String,
+    meaning: &'static str,
+}
+
+/// Prints what each merge state and review indicator of the PR list means
+pub fn legend() -> crate::error::Result<()> {
+    if let Some(&crate::config::Format::Json) = crate::config::FORMAT.get() {
+        return print_legend_json();
+    }
+    for status in &MergeStateStatus::ALL {
+        let value = serde_json::to_value(status)?;
+        outln!(
+            "{} {} {}",
+            status.colorize(&format!("{:<18}", value.as_str().unwrap_or_default())),
+            status.indicator(),
+            status.meaning()
+        );
+    }
+    for decision in &ReviewDecision::ALL {
+        let value = serde_json::to_value(decision)?;
+        outln!(
+            "{:<18} {} {}",
+            value.as_str().unwrap_or_default(),
+            decision.to_tag(),
+            decision.meaning()
+        );
+    }
+    outln!(
+        "{:<18} {} no review decision (with --show-no-review)",
+        "",
+        NO_REVIEW_TAG
+    );
+    Ok(())
+}
+
+fn print_legend_json() -> crate::error::Result<()> {
+    let mut entries = Vec::new();
+    for status in &MergeStateStatus::ALL {
+        entries.push(LegendEntry {
+            kind: "mergeStateStatus",
+            value: serde_json::to_value(status)?,
+            symbol: status.indicator(),
+            meaning: status.meaning(),
+        });
+    }
+    for decision in &ReviewDecision::ALL {
+        entries.push(LegendEntry {
+            kind: "reviewDecision",
+            value: serde_json::to_value(decision)?,
+            symbol: decision.to_tag().to_owned(),
+            meaning: decision.meaning(),
+        });
+    }
+    entries.push(LegendEntry {
+        kind: "reviewDecision",
+        value: serde_json::Value::Null,
+        symbol: NO_REVIEW_TAG.to_owned(),
+        meaning: "no review decision (with --show-no-review)",
+    });
+    outln!("{}", crate::cmd::to_json(&entries)?);
+    Ok(())
+}
+
+/// Counts of PRs by merge state and review decision, keyed by their GraphQL names
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct Summary {
+    merge_state_status: BTreeMap<String, usize>,
+    review_decision: BTreeMap<String, usize>,
+}
+
+fn summarize(fetched: &mut Fetched) -> crate::error::Result<Summary> {
+    let mut summary = Summary::default();
+    for prs in fetched.pull_requests_mut() {
+        for pr in prs.iter() {
+            if let Some(status) = &pr.merge_state_status {
+                *summary
+                    .merge_state_status
+                    .entry(enum_name(status)?)
+                    .or_default() += 1;
+            }
+            let decision = match &pr.review_decision {
+                Some(decision) => enum_name(decision)?,
+                None => "NONE".to_owned(),
+            };
+            *summary.review_decision.entry(decision).or_default() += 1;
         }
     }
+    Ok(summary)
+}
+
+fn enum_name<T: Serialize>(value: &T) -> crate::error::Result<String> {
+    let v = serde_json::to_value(value)?;
+    Ok(v.as_str().unwrap_or_default().to_owned())
+}
+
+fn print_summary(fetched: &mut Fetched) -> crate::error::Result<()> {
+    let summary = summarize(fetched)?;
+    if let Some(&crate::config::Format::Json) = crate::config::FORMAT.get() {
+        outln!("{}", crate::cmd::to_json(&summary)?);
+        return Ok(());
+    }
+    let line = |counts: &BTreeMap<String, usize>| {
+        counts
+            .iter()
+            .map(|(name, n)| format!("{}: {}", name.to_lowercase().replace('_', "-"), n))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let states = line(&summary.merge_state_status);
+    let reviews = line(&summary.review_decision);
+    match (states.is_empty(), reviews.is_empty()) {
+        (_, true) => outln!("no PRs"),
+        (true, false) => outln!("{}", reviews),
+        (false, false) => outln!("{} / {}", states, reviews),
+    }
     Ok(())
 }
 
-async fn check_owner(owner: &str) -> surf::Result<()> {
-    let v = json!({ "login": owner });
-    let q = json!({ "query": include_str!("../query/prs.graphql"), "variables": v });
-    let res = crate::graphql::query::<res::Res>(&q).await?;
-    match crate::config::FORMAT.get() {
-        Some(&crate::config::Format::Json) => println!("{}", serde_json::to_string_pretty(&res)?),
-        _ => print_owner_text(&res),
+fn print(fetched: &Fetched, args: &Args) -> crate::error::Result<()> {
+    match (crate::config::FORMAT.get(), fetched) {
+        (Some(&crate::config::Format::Json), Fetched::Owner(res)) => {
+            outln!("{}", crate::cmd::to_json(res)?)
+        }
+        (Some(&crate::config::Format::Json), Fetched::Repo(res)) => {
+            outln!("{}", crate::cmd::to_json(res)?)
+        }
+        (_, Fetched::Owner(res)) => print_owner_text(res, args),
+        (_, Fetched::Repo(res)) => print_repo_text(res, args),
     }
     Ok(())
 }
 
-fn print_owner_text(res: &res::Res) {
+async fn fetch_owner(
+    owner: &str,
+    args: &Args,
+    after: Option<&str>,
+) -> crate::error::Result<res::Res> {
+    let mut v = args.repo_filter.variables();
+    v["login"] = json!(owner);
+    v["after"] = json!(after);
+    v["mergeInfo"] = json!(!args.fast);
+    let query = include_str!("../query/prs.graphql").to_owned() + PULL_REQUEST_FRAGMENT;
+    let q = json!({ "query": query, "variables": v });
+    Ok(crate::graphql::query::<res::Res>(&q).await?)
+}
+
+fn print_owner_text(res: &res::Res, args: &Args) {
     let mut count = 0usize;
     for repo in &res.data.repository_owner.repositories.nodes {
         if repo.pull_requests.nodes.is_empty() {
             continue;
         }
-        println!("{}", repo.name.cyan());
+        outln!("{} ({})", repo.name.cyan(), repo.pull_requests.nodes.len());
         for pr in &repo.pull_requests.nodes {
             count += 1;
-            println!("{pr}");
+            print_pr(pr, args);
         }
     }
-    println!("Count of PRs: {count}");
+    outln!("Count of PRs: {count}");
 }
 
-async fn check_repo(owner: &str, name: &str) -> surf::Result<()> {
-    let v = json!({ "login": owner, "name": name });
-    let q = json!({ "query": include_str!("../query/prs.repo.graphql"), "variables": v });
-    let res = crate::graphql::query::<repo_res::RepoRes>(&q).await?;
-    match crate::config::FORMAT.get() {
-        Some(&crate::config::Format::Json) => println!("{}", serde_json::to_string_pretty(&res)?),
-        _ => print_repo_text(&res),
+fn print_pr(pr: &PullRequest, args: &Args) {
+    // review decisions are not fetched with `--fast`
+    let line = pr.render(&args.columns, args.show_no_review && !args.fast);
+    match args.stale_days {
+        Some(days) if pr.is_stale(days) && crate::config::emoji() => outln!("{line} ⏳"),
+        Some(days) if pr.is_stale(days) => outln!("{line} [stale]"),
+        _ => outln!("{line}"),
+    }
+    if args.print_checkout {
+        outln!("{:>6} {}", "", pr.checkout_commands().dimmed());
     }
-    Ok(())
 }
 
-fn print_repo_text(res: &repo_res::RepoRes) {
+async fn fetch_repo(
+    owner: &str,
+    name: &str,
+    args: &Args,
+    after: Option<&str>,
+) -> crate::error::Result<repo_res::RepoRes> {
+    let v = json!({ "login": owner, "name": name, "mergeInfo": !args.fast, "after": after });
+    let query = include_str!("../query/prs.repo.graphql").to_owned() + PULL_REQUEST_FRAGMENT;
+    let q = json!({ "query": query, "variables": v });
+    Ok(crate::graphql::query::<repo_res::RepoRes>(&q).await?)
+}
+
+fn print_repo_text(res: &repo_res::RepoRes, args: &Args) {
     let mut count = 0usize;
     for pr in &res.data.repository_owner.repository.pull_requests.nodes {
         count += 1;
-        println!("{pr}");
+        print_pr(pr, args);
+    }
+    outln!("Count of PRs: {count}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(review_decision: serde_json::Value) -> PullRequest {
+        serde_json::from_value(json!({
+            "number": 1,
+            "title": "title",
+            "url": "https://github.com/octo/hello/pull/1",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "isDraft": false,
+            "author": { "login": "octocat" },
+            "headRefName": "feature",
+            "baseRefName": "main",
+            "isCrossRepository": false,
+            "headRepositoryOwner": null,
+            "mergeStateStatus": "CLEAN",
+            "reviewDecision": review_decision,
+            "comments": { "totalCount": 0 },
+            "reviewThreads": { "totalCount": 0 }
+        }))
+        .expect("pullrequest fixture")
+    }
+
+    #[test]
+    fn renderers_agree_on_review_status() {
+        for (decision, tag) in [
+            (json!("APPROVED"), "[+]"),
+            (json!("CHANGES_REQUESTED"), "[!]"),
+            (json!("REVIEW_REQUIRED"), "[?]"),
+        ] {
+            let pr = pr(decision);
+            let line = pr.render(DEFAULT_COLUMNS, true);
+            assert!(line.contains(tag), "{}", line);
+            assert_eq!(format!("{:#}", pr), line);
+            assert_eq!(format!("{}", pr), pr.render(DEFAULT_COLUMNS, false));
+            assert_eq!(format!("{}", pr), line);
+        }
+    }
+
+    #[test]
+    fn renderers_agree_on_missing_review() {
+        let pr = pr(serde_json::Value::Null);
+        let line = pr.render(DEFAULT_COLUMNS, true);
+        assert!(line.contains(NO_REVIEW_TAG), "{}", line);
+        assert_eq!(format!("{:#}", pr), line);
+        let plain = format!("{}", pr);
+        assert!(!plain.contains(NO_REVIEW_TAG), "{}", plain);
+        assert_eq!(plain, pr.render(DEFAULT_COLUMNS, false));
     }
-    println!("Count of PRs: {count}");
 }
@@ -0,0 +1,191 @@
+use colored::Colorize;
+use serde_json::json;
+
+nestruct::nest! {
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    IdRes {
+        data: {
+            repository: {
+                pull_request: {
+                    id: String
+                }
+            }
+        }
+    }
+}
+
+nestruct::nest! {
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    ApproveRes {
+        data: {
+            add_pull_request_review: {
+                pull_request_review: {
+                    state: String
+                }
+            }
+        }
+    }
+}
+
+nestruct::nest! {
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    MergeRes {
+        data: {
+            merge_pull_request: {
+                pull_request: {
+                    merged: bool
+                }
+            }
+        }
+    }
+}
+
+nestruct::nest! {
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    AutoMergeRes {
+        data: {
+            enable_pull_request_auto_merge: {
+                pull_request: {
+                    auto_merge_request: serde_json::Value
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct MergeArgs {
+    slug: String,
+    num: usize,
+    /// Enable auto-merge so GitHub merges once the requirements are met
+    #[clap(long = "auto")]
+    auto: bool,
+    /// Merge method to use
+    #[clap(long = "method", default_value = "merge")]
+    method: MergeMethod,
+}
+
+#[derive(Debug, Clone, serde::Serialize, clap::ValueEnum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+/// Approves the pullrequest `num` of the repository `slug`
+pub async fn approve(slug: &str, num: usize) -> crate::error::Result<()> {
+    let res = approve_pr(slug, num).await;
+    crate::audit::record("approve", slug, num, &res).await;
+    res
+}
+
+async fn approve_pr(slug: &str, num: usize) -> crate::error::Result<()> {
+    let id = resolve_id(slug, num).await?;
+    let q = json!({ "query": include_str!("../query/approve.graphql"), "variables": { "id": id } });
+    let res = crate::graphql::query::<approve_res::ApproveRes>(&q).await?;
+    let state = res.data.add_pull_request_review.pull_request_review.state;
+    outln!("{}#{} {}", slug.cyan(), num, state.green());
+    Ok(())
+}
+
+/// Merges the pullrequest, or enables auto-merge on it with `--auto`
+pub async fn merge(args: &MergeArgs) -> crate::error::Result<()> {
+    let res = merge_pr(args).await;
+    let action = if args.auto {
+        "enable_auto_merge"
+    } else {
+        "merge"
+    };
+    crate::audit::record(action, &args.slug, args.num, &res).await;
+    res
+}
+
+async fn merge_pr(args: &MergeArgs) -> crate::error::Result<()> {
+    merge_or_enable(args).await.map_err(explain_protection)
+}
+
+async fn merge_or_enable(args: &MergeArgs) -> crate::error::Result<()> {
+    let (slug, num) = (args.slug.as_str(), args.num);
+    let id = resolve_id(slug, num).await?;
+    if args.auto {
+        return enable_auto_merge(slug, num, &id, &args.method).await;
+    }
+    let v = json!({ "id": id, "method": args.method });
+    let q = json!({ "query": include_str!("../query/merge.graphql"), "variables": v });
+    let res = crate::graphql::query::<merge_res::MergeRes>(&q).await?;
+    if res.data.merge_pull_request.pull_request.merged {
+        outln!("{}#{} {}", slug.cyan(), num, "MERGED".magenta());
+        Ok(())
+    } else {
+        Err(crate::error::Error::InvalidInput(format!(
+            "{}#{} was not merged",
+            slug, num
+        )))
+    }
+}
+
+/// Rewrites a merge rejected by branch protection into a message saying what is missing
+fn explain_protection(e: crate::error::Error) -> crate::error::Error {
+    let err = match e {
+        crate::error::Error::Network(err) => err,
+        e => return e,
+    };
+    let msg = err.to_string();
+    let lower = msg.to_lowercase();
+    let reason = if let Some(n) = required_approvals(&lower) {
+        format!("{} approval{} required", n, if n == 1 { "" } else { "s" })
+    } else if lower.contains("required status check") {
+        "required status checks have not passed".to_owned()
+    } else if lower.contains("code owner") {
+        "a review from a code owner is required".to_owned()
+    } else if lower.contains("protected branch") || lower.contains("rule violation") {
+        msg.clone()
+    } else {
+        return crate::error::Error::Network(err);
+    };
+    let status = err.status();
+    crate::error::Error::Network(surf::Error::from_str(
+        status,
+        format!("merge blocked by branch protection: {}", reason),
+    ))
+}
+
+/// N of GitHub's "At least N approving reviews are required" rejection
+fn required_approvals(msg: &str) -> Option<usize> {
+    let rest = msg.split("at least ").nth(1)?;
+    let (n, rest) = rest.split_once(' ')?;
+    rest.starts_with("approving review")
+        .then(|| n.parse().ok())?
+}
+
+async fn enable_auto_merge(
+    slug: &str,
+    num: usize,
+    id: &str,
+    method: &MergeMethod,
+) -> crate::error::Result<()> {
+    let v = json!({ "id": id, "method": method });
+    let q = json!({ "query": include_str!("../query/automerge.graphql"), "variables": v });
+    crate::graphql::query::<auto_merge_res::AutoMergeRes>(&q).await?;
+    outln!("{}#{} {}", slug.cyan(), num, "AUTO-MERGE ENABLED".green());
+    Ok(())
+}
+
+async fn resolve_id(slug: &str, num: usize) -> crate::error::Result<String> {
+    let vs: Vec<&str> = slug.split('/').collect();
+    if vs.len() != 2 {
+        return Err(crate::error::Error::InvalidInput(format!(
+            "unknown slug format: {}",
+            slug
+        )));
+    }
+    let v = json!({ "owner": vs[0], "name": vs[1], "number": num });
+    let q = json!({ "query": include_str!("../query/pullrequest.id.graphql"), "variables": v });
+    let res = crate::graphql::query::<id_res::IdRes>(&q).await?;
+    Ok(res.data.repository.pull_request.id)
+}
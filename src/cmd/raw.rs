@@ -0,0 +1,73 @@
+#[derive(Debug, clap::Parser)]
+pub struct GraphqlArgs {
+    /// File containing the GraphQL query
+    query_file: std::path::PathBuf,
+    /// Query variable as key=value; the value is parsed as JSON, else taken as a string
+    #[clap(long = "var", value_parser = parse_pair)]
+    vars: Vec<(String, serde_json::Value)>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct RestArgs {
+    #[clap(value_enum, ignore_case = true)]
+    method: Method,
+    /// Path below the API root, like repos/OWNER/REPO/labels
+    path: String,
+    /// Field as key=value, sent as a query parameter of GET or in the JSON body otherwise;
+    /// the value is parsed as JSON, else taken as a string
+    #[clap(long = "field", short = 'F', value_parser = parse_pair)]
+    fields: Vec<(String, serde_json::Value)>,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+enum Method {
+    Get,
+    Post,
+    Patch,
+    Put,
+    Delete,
+}
+
+impl From<&Method> for surf::http::Method {
+    fn from(method: &Method) -> Self {
+        match method {
+            Method::Get => surf::http::Method::Get,
+            Method::Post => surf::http::Method::Post,
+            Method::Patch => surf::http::Method::Patch,
+            Method::Put => surf::http::Method::Put,
+            Method::Delete => surf::http::Method::Delete,
+        }
+    }
+}
+
+/// Runs the query of the file and prints the whole response
+pub async fn graphql(args: &GraphqlArgs) -> crate::error::Result<()> {
+    let query = std::fs::read_to_string(&args.query_file)?;
+    let vars: serde_json::Map<String, serde_json::Value> = args.vars.iter().cloned().collect();
+    let q = serde_json::json!({ "query": query, "variables": vars });
+    let res = crate::graphql::query::<serde_json::Value>(&q).await?;
+    outln!("{}", serde_json::to_string_pretty(&res)?);
+    Ok(())
+}
+
+/// Sends the request and prints the response body, if any
+pub async fn rest(args: &RestArgs) -> crate::error::Result<()> {
+    let fields = args.fields.iter().cloned().collect();
+    let mut res = crate::rest::request((&args.method).into(), &args.path, &fields).await?;
+    let body = res.body_string().await?;
+    // e.g. 204 No Content of a DELETE
+    if body.trim().is_empty() {
+        return Ok(());
+    }
+    let v: serde_json::Value = serde_json::from_str(&body)?;
+    outln!("{}", serde_json::to_string_pretty(&v)?);
+    Ok(())
+}
+
+fn parse_pair(s: &str) -> Result<(String, serde_json::Value), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected key=value: {}", s))?;
+    let value = serde_json::from_str(value).unwrap_or_else(|_| value.into());
+    Ok((key.to_owned(), value))
+}
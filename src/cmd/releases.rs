@@ -0,0 +1,70 @@
+use colored::Colorize;
+use serde_json::json;
+
+nestruct::nest! {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    Res {
+        data: {
+            repository: {
+                releases: {
+                    nodes: [{
+                        tag_name: String,
+                        name: Option<String>,
+                        published_at: Option<String>,
+                        is_prerelease: bool,
+                        url: String,
+                    }]
+                }
+            }
+        }
+    }
+}
+
+type Release = res::data::repository::releases::nodes::Nodes;
+
+#[derive(Debug, clap::Parser)]
+pub struct Args {
+    slug: String,
+    /// Number of recent releases to show
+    #[clap(long = "limit", default_value = "10", value_parser = clap::value_parser!(u8).range(1..=100))]
+    limit: u8,
+}
+
+pub async fn list(args: &Args) -> crate::error::Result<()> {
+    let vs: Vec<&str> = args.slug.split('/').collect();
+    if vs.len() != 2 {
+        return Err(crate::error::Error::InvalidInput(format!(
+            "unknown slug format: {}",
+            args.slug
+        )));
+    }
+    let v = json!({ "owner": vs[0], "name": vs[1], "limit": args.limit });
+    let q = json!({ "query": include_str!("../query/releases.graphql"), "variables": v });
+    let res = crate::graphql::query::<res::Res>(&q).await?;
+    let releases = res.data.repository.releases.nodes;
+    match crate::config::FORMAT.get() {
+        Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(&releases)?),
+        _ => print_text(&releases),
+    }
+    Ok(())
+}
+
+fn print_text(releases: &[Release]) {
+    for release in releases {
+        let published = match &release.published_at {
+            Some(at) => at.get(..10).unwrap_or(at).to_owned(),
+            None => "draft".to_owned(),
+        };
+        out!("{} {}", published.dimmed(), release.tag_name.cyan());
+        if release.is_prerelease {
+            out!(" {}", "prerelease".yellow());
+        }
+        outln!(
+            " {} {}",
+            release.name.as_deref().unwrap_or_default(),
+            release.url
+        );
+    }
+    outln!("Count of releases: {}", releases.len());
+}
@@ -0,0 +1,84 @@
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+nestruct::nest! {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    Res {
+        data: {
+            repository: {
+                name_with_owner: String,
+                description: Option<String>,
+                url: String,
+                stargazer_count: usize,
+                fork_count: usize,
+                issues: {
+                    total_count: usize,
+                },
+                pull_requests: {
+                    total_count: usize,
+                },
+                default_branch_ref: Option<crate::cmd::repo::Named>,
+                primary_language: Option<crate::cmd::repo::Named>,
+            }
+        }
+    }
+}
+
+type Repository = res::data::repository::Repository;
+
+#[derive(Serialize, Deserialize)]
+struct Named {
+    name: String,
+}
+
+pub async fn show(slug: &str) -> crate::error::Result<()> {
+    let vs: Vec<&str> = slug.split('/').collect();
+    if vs.len() != 2 {
+        return Err(crate::error::Error::InvalidInput(format!(
+            "unknown slug format: {}",
+            slug
+        )));
+    }
+    let v = json!({ "owner": vs[0], "name": vs[1] });
+    let q = json!({ "query": include_str!("../query/repo.graphql"), "variables": v });
+    let res = crate::graphql::query::<res::Res>(&q).await?;
+    match crate::config::FORMAT.get() {
+        Some(&crate::config::Format::Json) => {
+            outln!("{}", crate::cmd::to_json(&res.data.repository)?)
+        }
+        _ => print_text(&res.data.repository),
+    }
+    Ok(())
+}
+
+fn print_text(repo: &Repository) {
+    let none = || "-".to_owned();
+    outln!("{}", repo.name_with_owner.bright_blue());
+    let rows = [
+        ("description", repo.description.clone().unwrap_or_else(none)),
+        ("url", repo.url.clone()),
+        ("stars", repo.stargazer_count.to_string()),
+        ("forks", repo.fork_count.to_string()),
+        ("open issues", repo.issues.total_count.to_string()),
+        ("open prs", repo.pull_requests.total_count.to_string()),
+        (
+            "default branch",
+            repo.default_branch_ref
+                .as_ref()
+                .map(|r| r.name.clone())
+                .unwrap_or_else(none),
+        ),
+        (
+            "language",
+            repo.primary_language
+                .as_ref()
+                .map(|l| l.name.clone())
+                .unwrap_or_else(none),
+        ),
+    ];
+    for (key, value) in rows {
+        outln!("  {:<15} {}", key.cyan(), value);
+    }
+}
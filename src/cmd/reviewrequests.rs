@@ -0,0 +1,58 @@
+use colored::Colorize;
+use serde_json::json;
+
+nestruct::nest! {
+    #[derive(serde::Serialize, serde::Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    Res {
+        data: {
+            search: {
+                nodes: [{
+                    #[serde(flatten)]
+                    pull_request: crate::cmd::prs::PullRequest,
+                    repository: {
+                        name_with_owner: String,
+                    },
+                }]
+            }
+        }
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Args {
+    /// Skip merge state and review decision lookups for a quicker listing
+    #[clap(long = "fast", alias = "no-merge-info")]
+    fast: bool,
+}
+
+pub async fn check(args: &Args) -> crate::error::Result<()> {
+    let v = json!({ "q": "is:pr is:open review-requested:@me", "mergeInfo": !args.fast });
+    let query = include_str!("../query/reviewrequests.graphql").to_owned()
+        + crate::cmd::prs::PULL_REQUEST_FRAGMENT;
+    let q = json!({ "query": query, "variables": v });
+    let res = crate::graphql::query::<res::Res>(&q).await?;
+    match crate::config::FORMAT.get() {
+        Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(&res)?),
+        _ => print_text(&res),
+    }
+    Ok(())
+}
+
+fn print_text(res: &res::Res) {
+    let mut repos: Vec<(&str, Vec<&crate::cmd::prs::PullRequest>)> = Vec::new();
+    for node in &res.data.search.nodes {
+        let name = node.repository.name_with_owner.as_str();
+        match repos.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, prs)) => prs.push(&node.pull_request),
+            None => repos.push((name, vec![&node.pull_request])),
+        }
+    }
+    for (name, prs) in &repos {
+        outln!("{}", name.cyan());
+        for pr in prs {
+            outln!("{pr}");
+        }
+    }
+    outln!("Count of PRs: {}", res.data.search.nodes.len());
+}
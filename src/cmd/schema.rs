@@ -0,0 +1,24 @@
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum Target {
+    Prs,
+    Issues,
+    Notifications,
+}
+
+pub fn print(target: &Target) -> crate::error::Result<()> {
+    let schema = match target {
+        Target::Prs => crate::cmd::prs::schema(),
+        Target::Issues => crate::cmd::issues::schema(),
+        Target::Notifications => crate::cmd::notifications::schema(),
+    };
+    outln!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+/// Schema of `T` as it is serialized, leaving out `skip_serializing` fields
+pub fn serialized<T: schemars::JsonSchema>() -> schemars::Schema {
+    schemars::generate::SchemaSettings::default()
+        .for_serialize()
+        .into_generator()
+        .into_root_schema_for::<T>()
+}
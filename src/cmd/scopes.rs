@@ -0,0 +1,39 @@
+use colored::Colorize;
+use serde_json::json;
+
+const REQUIRED: [&str; 2] = ["repo", "notifications"];
+
+pub async fn check() -> crate::error::Result<()> {
+    let granted = crate::rest::scopes().await?;
+    let missing: Vec<&str> = REQUIRED
+        .iter()
+        .filter(|s| !granted.iter().any(|g| g == *s))
+        .copied()
+        .collect();
+    match crate::config::FORMAT.get() {
+        Some(&crate::config::Format::Json) => {
+            let v = json!({ "granted": granted, "required": REQUIRED, "missing": missing });
+            outln!("{}", crate::cmd::to_json(&v)?)
+        }
+        _ => print_text(&granted, &missing),
+    }
+    Ok(())
+}
+
+fn print_text(granted: &[String], missing: &[&str]) {
+    if granted.is_empty() {
+        outln!(
+            "{}",
+            "no scopes reported (fine-grained tokens do not list scopes)".yellow()
+        );
+    } else {
+        outln!("granted: {}", granted.join(", ").cyan());
+    }
+    for scope in REQUIRED {
+        if missing.contains(&scope) {
+            outln!("  {} {}", "✗".red(), scope);
+        } else {
+            outln!("  {} {}", "✓".green(), scope);
+        }
+    }
+}
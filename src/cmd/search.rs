@@ -1,4 +1,3 @@
-use crate::config::TOKEN;
 use colored::Colorize;
 
 nestruct::nest! {
@@ -31,6 +30,18 @@ pub struct Query {
     /// Search by language
     #[clap(long, short)]
     language: Option<String>,
+    /// Page of the results, starting at 1
+    #[clap(long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+    page: u32,
+    /// Number of results per page, at most 100
+    #[clap(long, default_value = "100", value_parser = clap::value_parser!(u8).range(1..=100))]
+    per_page: u8,
+    /// Show the relevance score of each result
+    #[clap(long)]
+    score: bool,
+    /// Order results by descending relevance score
+    #[clap(long)]
+    sort_score: bool,
 }
 
 impl Query {
@@ -48,8 +59,8 @@ impl Query {
             .as_str();
         ApiQuery {
             q,
-            page: 0,
-            per_page: 100,
+            page: self.page,
+            per_page: self.per_page,
         }
     }
 }
@@ -57,33 +68,101 @@ impl Query {
 #[derive(Debug, clap::Parser, serde::Serialize)]
 struct ApiQuery {
     q: String,
-    page: usize,
+    page: u32,
     per_page: u8,
 }
 
-pub async fn search(q: &Query) -> surf::Result<()> {
-    let mut res = surf::get("https://api.github.com/search/code")
-        .header("Authorization", format!("token {}", *TOKEN))
-        .query(&q.to_api())?
-        .await?;
-    let search_result = res.body_json::<search::Search>().await?;
+pub async fn search(q: &Query) -> crate::error::Result<()> {
+    if crate::config::dry_run() {
+        eprintln!(
+            "dry-run: GET {}search/code query={:?}",
+            crate::config::api_root(),
+            q.to_api()
+        );
+        return Ok(());
+    }
+    let mut res = match crate::mock::response("search/code") {
+        Some(res) => res?,
+        None => {
+            let req = crate::client::CLIENT
+                .get(crate::config::api_root() + "search/code")
+                .query(&q.to_api())?;
+            crate::client::send("GET search/code", req).await?
+        }
+    };
+    let mut search_result = res.body_json::<search::Search>().await?;
+    if q.sort_score {
+        search_result
+            .items
+            .sort_by(|a, b| b.score.total_cmp(&a.score));
+    }
     match crate::config::FORMAT.get() {
         Some(&crate::config::Format::Json) => {
-            println!("{}", serde_json::to_string_pretty(&search_result)?)
+            outln!("{}", crate::cmd::to_json(&search_result)?)
         }
-        _ => print_text(&search_result),
+        _ => print_text(&search_result, q),
     }
     Ok(())
 }
 
-fn print_text(res: &search::Search) {
+fn print_text(res: &search::Search, q: &Query) {
     for n in &res.items {
-        println!(
+        if q.score {
+            out!("{} ", format!("{:>8.2}", n.score).magenta());
+        }
+        outln!(
             "{} {} {}",
             n.repository.full_name.cyan(),
-            n.path.yellow(),
+            crate::output::hyperlink(&n.path.yellow().to_string(), &n.html_url),
             n.html_url
         )
     }
-    println!("# count: {}", res.items.len());
+    outln!("# count: {}", res.items.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn query_string(args: &[&str]) -> String {
+        let q = Query::try_parse_from(args).expect("valid arguments");
+        let req = surf::get("https://api.github.com/search/code")
+            .query(&q.to_api())
+            .expect("encode query")
+            .build();
+        req.url().query().unwrap_or_default().to_owned()
+    }
+
+    #[test]
+    fn defaults_to_first_full_page() {
+        assert_eq!(
+            query_string(&["search", "fn main", "--user", "octo"]),
+            "q=fn+main+user%3Aocto&page=1&per_page=100"
+        );
+    }
+
+    #[test]
+    fn passes_page_and_per_page() {
+        assert_eq!(
+            query_string(&[
+                "search",
+                "x",
+                "-l",
+                "rust",
+                "--page",
+                "3",
+                "--per-page",
+                "20"
+            ]),
+            "q=x+language%3Arust&page=3&per_page=20"
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_range_pages() {
+        assert!(Query::try_parse_from(["search", "x", "--page", "0"]).is_err());
+        assert!(Query::try_parse_from(["search", "x", "--per-page", "101"]).is_err());
+        assert!(Query::try_parse_from(["search", "x", "--per-page", "0"]).is_err());
+    }
 }
@@ -0,0 +1,59 @@
+use colored::Colorize;
+use serde_json::json;
+
+nestruct::nest! {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    Res {
+        data: {
+            user: {
+                starred_repositories: {
+                    page_info: crate::cmd::PageInfo,
+                    nodes: [{
+                        name_with_owner: String,
+                        description: Option<String>,
+                        url: String,
+                    }]
+                }
+            }
+        }
+    }
+}
+
+type Repository = res::data::user::starred_repositories::nodes::Nodes;
+
+pub async fn list(user: Option<&str>) -> crate::error::Result<()> {
+    let login = match user {
+        Some(user) => user.to_owned(),
+        None => crate::cmd::viewer::get().await?,
+    };
+    let mut repos = Vec::new();
+    let mut after: Option<String> = None;
+    loop {
+        let v = json!({ "login": login, "after": after });
+        let q = json!({ "query": include_str!("../query/starred.graphql"), "variables": v });
+        let res = crate::graphql::query::<res::Res>(&q).await?;
+        let starred = res.data.user.starred_repositories;
+        repos.extend(starred.nodes);
+        match starred.page_info.next() {
+            Some(cursor) => after = Some(cursor.to_owned()),
+            None => break,
+        }
+    }
+    match crate::config::FORMAT.get() {
+        Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(&repos)?),
+        _ => print_text(&repos),
+    }
+    Ok(())
+}
+
+fn print_text(repos: &[Repository]) {
+    for repo in repos {
+        outln!(
+            "{} {}",
+            repo.name_with_owner.cyan(),
+            repo.description.as_deref().unwrap_or_default()
+        );
+    }
+    outln!("Count of starred repositories: {}", repos.len());
+}
@@ -67,20 +67,23 @@ impl std::fmt::Display for Assignee {
     }
 }
 
-pub async fn track(slug: &str, num: usize) -> surf::Result<()> {
+pub async fn track(slug: &str, num: usize) -> crate::error::Result<()> {
     let vs: Vec<String> = slug.split('/').map(String::from).collect();
     match vs.len() {
         2 => track_issue(&vs[0], &vs[1], num).await,
-        _ => panic!("unknown slug format"),
+        _ => Err(crate::error::Error::InvalidInput(format!(
+            "unknown slug format: {}",
+            slug
+        ))),
     }
 }
 
-async fn track_issue(owner: &str, name: &str, num: usize) -> surf::Result<()> {
+async fn track_issue(owner: &str, name: &str, num: usize) -> crate::error::Result<()> {
     let v = json!({ "owner": owner, "name": name, "number": num });
     let q = json!({ "query": include_str!("../query/trackassignees.graphql"), "variables": v });
     let res: Res = crate::graphql::query::<Res>(&q).await?;
     match crate::config::FORMAT.get() {
-        Some(&crate::config::Format::Json) => println!("{}", serde_json::to_string_pretty(&res)?),
+        Some(&crate::config::Format::Json) => outln!("{}", crate::cmd::to_json(&res)?),
         _ => print_text(&res, owner, name),
     }
     Ok(())
@@ -88,7 +91,7 @@ async fn track_issue(owner: &str, name: &str, num: usize) -> surf::Result<()> {
 
 fn print_text(res: &Res, owner: &str, name: &str) {
     let (mut maxcount, mut count) = (0isize, 0isize);
-    println!(
+    outln!(
         "{}/{}#{} {}",
         owner.cyan(),
         name.cyan(),
@@ -102,12 +105,12 @@ fn print_text(res: &Res, owner: &str, name: &str) {
             -1
         };
         maxcount = maxcount.max(count);
-        println!(
+        outln!(
             "  {} \t{}\t{}",
             item.__typename,
             item.createdAt.bright_black(),
             item.assignee
         );
     }
-    println!("Count of Max assignees: {maxcount}");
+    outln!("Count of Max assignees: {maxcount}");
 }
@@ -1,4 +1,5 @@
 use serde_json::json;
+use std::sync::OnceLock;
 
 nestruct::nest! {
     #[derive(serde::Deserialize, serde::Serialize)]
@@ -11,8 +12,22 @@ nestruct::nest! {
     }
 }
 
-pub async fn get() -> surf::Result<String> {
+/// Stand-in for the viewer login under `--dry-run`
+const DRY_RUN_LOGIN: &str = "<viewer>";
+
+/// Login of the viewer, resolved once per process
+static LOGIN: OnceLock<String> = OnceLock::new();
+
+pub async fn get() -> crate::error::Result<String> {
+    if let Some(login) = LOGIN.get() {
+        return Ok(login.clone());
+    }
     let q = json!({ "query": include_str!("../query/viewer.graphql") });
-    let res = crate::graphql::query::<res::Res>(&q).await?;
-    Ok(res.data.viewer.login)
+    let login = match crate::graphql::query::<res::Res>(&q).await {
+        Ok(res) => res.data.viewer.login,
+        // the queries needing the login are still worth printing
+        Err(e) if e.downcast_ref::<crate::graphql::DryRun>().is_some() => DRY_RUN_LOGIN.to_owned(),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(LOGIN.get_or_init(|| login).clone())
 }
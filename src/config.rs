@@ -1,14 +1,20 @@
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+pub const DEFAULT_HOST: &str = "github.com";
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Config {
+    /// Token of `DEFAULT_HOST` written by older versions
     pub token: Option<String>,
+    /// Tokens keyed by host
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hosts: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -19,7 +25,10 @@ pub enum Format {
 
 impl Config {
     pub fn new() -> Self {
-        Self { token: None }
+        Self {
+            token: None,
+            hosts: BTreeMap::new(),
+        }
     }
 
     pub fn from_path(p: &Path) -> Self {
@@ -29,6 +38,39 @@ impl Config {
             Err(_) => Self::new(),
         }
     }
+
+    pub fn save(&self, p: &Path) -> crate::error::Result<()> {
+        if let Some(dir) = p.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        Ok(std::fs::write(p, toml::to_string(self)?)?)
+    }
+
+    pub fn token_for(&self, host: &str) -> Option<String> {
+        match self.hosts.get(host) {
+            Some(tok) => Some(tok.clone()),
+            None if host == DEFAULT_HOST => self.token.clone(),
+            None => None,
+        }
+    }
+
+    /// Stores the token of the host, replacing a legacy token of the same host
+    pub fn set_token(&mut self, host: &str, token: String) {
+        if host == DEFAULT_HOST {
+            self.token = None;
+        }
+        self.hosts.insert(host.to_owned(), token);
+    }
+
+    /// Removes the token of the host, returning whether there was one
+    pub fn remove_token(&mut self, host: &str) -> bool {
+        let legacy = host == DEFAULT_HOST && self.token.take().is_some();
+        self.hosts.remove(host).is_some() || legacy
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.token.is_none() && self.hosts.is_empty()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -60,36 +102,162 @@ impl GHConfig {
     }
 }
 
-pub static CONFIG_PATH: Lazy<PathBuf> = Lazy::new(|| {
-    let mut path = match std::env::var("XDG_CONFIG_HOME") {
-        Ok(p) => PathBuf::from(p),
-        Err(_) => PathBuf::from(std::env::var("HOME").unwrap() + "/.config"),
-    };
+/// Config file given by `--config`, in place of the XDG location
+pub static CONFIG_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Directory holding the config files, `$XDG_CONFIG_HOME` or `~/.config`
+fn config_dir() -> crate::error::Result<PathBuf> {
+    if let Ok(p) = std::env::var("XDG_CONFIG_HOME") {
+        return Ok(PathBuf::from(p));
+    }
+    match std::env::var("HOME") {
+        Ok(home) => Ok(PathBuf::from(home + "/.config")),
+        Err(_) => Err(crate::error::Error::Config(
+            "neither XDG_CONFIG_HOME nor HOME is set".to_owned(),
+        )),
+    }
+}
+
+pub fn config_path() -> crate::error::Result<PathBuf> {
+    if let Some(path) = CONFIG_OVERRIDE.get() {
+        return Ok(path.clone());
+    }
+    let mut path = config_dir()?;
     path.push("gh-chk");
     path.push("config.toml");
-    path
-});
+    Ok(path)
+}
 
-pub static CONFIG: Lazy<Config> = Lazy::new(|| Config::from_path(&CONFIG_PATH));
+/// Config of gh-chk, empty when there is no config file or no place to look for one
+pub static CONFIG: Lazy<Config> = Lazy::new(|| match config_path() {
+    Ok(path) => Config::from_path(&path),
+    Err(_) => Config::new(),
+});
 
-pub static GH_CONFIG_PATH: Lazy<PathBuf> = Lazy::new(|| {
-    let mut path = match std::env::var("XDG_CONFIG_HOME") {
-        Ok(p) => PathBuf::from(p),
-        Err(_) => PathBuf::from(std::env::var("HOME").unwrap() + "/.config"),
-    };
+pub fn gh_config_path() -> crate::error::Result<PathBuf> {
+    let mut path = config_dir()?;
     path.push("gh");
     path.push("hosts.yml");
-    path
+    Ok(path)
+}
+
+pub static GH_CONFIG: Lazy<GHConfig> = Lazy::new(|| match gh_config_path() {
+    Ok(path) => GHConfig::from_path(&path),
+    Err(_) => GHConfig::new(),
 });
 
-pub static GH_CONFIG: Lazy<GHConfig> = Lazy::new(|| GHConfig::from_path(&GH_CONFIG_PATH));
+/// Token of a GitHub App installation, which takes precedence over personal tokens
+pub static INSTALLATION_TOKEN: OnceLock<String> = OnceLock::new();
+
+/// Host of the GitHub instance to query, set by `--hostname` or `GH_HOST`
+pub static HOST: OnceLock<String> = OnceLock::new();
+
+pub fn host() -> &'static str {
+    HOST.get().map_or(DEFAULT_HOST, String::as_str)
+}
+
+/// Root of the REST API of the host, ending with a slash
+pub fn api_root() -> String {
+    match host() {
+        DEFAULT_HOST => "https://api.github.com/".to_owned(),
+        host => format!("https://{}/api/v3/", host),
+    }
+}
 
-pub static TOKEN: Lazy<String> = Lazy::new(|| match GH_CONFIG.entries.get("github.com") {
-    Some(tok_conf) => tok_conf.oauth_token.clone(),
-    None => match CONFIG.token.clone() {
-        Some(tok) => tok,
-        None => std::env::var("GITHUB_TOKEN").unwrap_or_default(),
-    },
+/// Endpoint of the GraphQL API of the host
+pub fn graphql_uri() -> String {
+    match host() {
+        DEFAULT_HOST => "https://api.github.com/graphql".to_owned(),
+        host => format!("https://{}/api/graphql", host),
+    }
+}
+
+pub static TOKEN: Lazy<String> = Lazy::new(|| {
+    if let Some(tok) = INSTALLATION_TOKEN.get() {
+        return tok.clone();
+    }
+    let gh = || GH_CONFIG.entries.get(host()).map(|c| c.oauth_token.clone());
+    let own = || CONFIG.token_for(host());
+    // a file given by `--config` picks the profile, ahead of the gh CLI login
+    let tok = match CONFIG_OVERRIDE.get() {
+        Some(_) => own().or_else(gh),
+        None => gh().or_else(own),
+    };
+    tok.unwrap_or_else(|| std::env::var("GITHUB_TOKEN").unwrap_or_default())
 });
 
 pub static FORMAT: OnceLock<Format> = OnceLock::new();
+
+pub static BUDGET: OnceLock<usize> = OnceLock::new();
+
+pub static DRY_RUN: OnceLock<bool> = OnceLock::new();
+
+pub fn dry_run() -> bool {
+    DRY_RUN.get().copied().unwrap_or_default()
+}
+
+pub static COUNT_ONLY: OnceLock<bool> = OnceLock::new();
+
+pub fn count_only() -> bool {
+    COUNT_ONLY.get().copied().unwrap_or_default()
+}
+
+pub static FAIL_ON_EMPTY: OnceLock<bool> = OnceLock::new();
+
+/// Keys kept in each item of the JSON output
+pub static FIELDS: OnceLock<Vec<String>> = OnceLock::new();
+
+pub static EMOJI: OnceLock<bool> = OnceLock::new();
+
+/// Whether state indicators are drawn as emoji rather than ASCII tags
+pub fn emoji() -> bool {
+    EMOJI.get().copied().unwrap_or(true)
+}
+
+pub static OFFSET: OnceLock<time::UtcOffset> = OnceLock::new();
+
+/// Offset dates are shown in, UTC unless `--timezone` or `TZ` says otherwise
+pub fn offset() -> time::UtcOffset {
+    OFFSET.get().copied().unwrap_or(time::UtcOffset::UTC)
+}
+
+/// Parses `utc`, `local` or an offset like `+09:00`
+pub fn parse_timezone(s: &str) -> Result<time::UtcOffset, String> {
+    match s.to_ascii_lowercase().as_str() {
+        "utc" | "z" => return Ok(time::UtcOffset::UTC),
+        "local" => {
+            return time::UtcOffset::current_local_offset()
+                .map_err(|e| format!("local offset unavailable: {}", e))
+        }
+        _ => {}
+    }
+    let invalid = || format!("invalid timezone {:?}: expected utc, local or +HH:MM", s);
+    let (sign, rest) = match s.split_at_checked(1) {
+        Some(("+", rest)) => (1, rest),
+        Some(("-", rest)) => (-1, rest),
+        _ => return Err(invalid()),
+    };
+    let (h, m) = rest.split_once(':').unwrap_or((rest, "0"));
+    let h: i8 = h.parse().map_err(|_| invalid())?;
+    let m: i8 = m.parse().map_err(|_| invalid())?;
+    time::UtcOffset::from_hms(sign * h, sign * m, 0).map_err(|_| invalid())
+}
+
+pub static CONCURRENCY: OnceLock<usize> = OnceLock::new();
+
+/// Maximum number of API requests in flight at once
+pub fn concurrency() -> usize {
+    CONCURRENCY.get().copied().unwrap_or(4)
+}
+
+pub static TIMING: OnceLock<bool> = OnceLock::new();
+
+pub fn timing() -> bool {
+    TIMING.get().copied().unwrap_or_default()
+}
+
+pub static VERBOSE: OnceLock<bool> = OnceLock::new();
+
+pub fn verbose() -> bool {
+    VERBOSE.get().copied().unwrap_or_default()
+}
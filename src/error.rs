@@ -0,0 +1,63 @@
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("config error: {0}")]
+    Config(String),
+    #[error("{0}")]
+    Network(surf::Error),
+    #[error("failed to parse: {0}")]
+    Parse(String),
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("dry run")]
+    DryRun,
+    #[error("no items found")]
+    Empty,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Process exit code, so scripts can tell why a command failed
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::DryRun => 0,
+            Error::Empty => 1,
+            Error::InvalidInput(_) => 2,
+            Error::Network(_) | Error::Config(_) => 3,
+            Error::Parse(_) | Error::Io(_) => 4,
+        }
+    }
+}
+
+impl From<surf::Error> for Error {
+    fn from(e: surf::Error) -> Self {
+        if e.downcast_ref::<crate::graphql::DryRun>().is_some() {
+            return Error::DryRun;
+        }
+        // responses that did not deserialize arrive wrapped by surf
+        if let Some(parse) = e.downcast_ref::<serde_json::Error>() {
+            return Error::Parse(parse.to_string());
+        }
+        Error::Network(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parse(e.to_string())
+    }
+}
+
+impl From<std::num::ParseIntError> for Error {
+    fn from(e: std::num::ParseIntError) -> Self {
+        Error::Parse(e.to_string())
+    }
+}
+
+impl From<toml::ser::Error> for Error {
+    fn from(e: toml::ser::Error) -> Self {
+        Error::Config(e.to_string())
+    }
+}
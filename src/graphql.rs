@@ -1,13 +1,143 @@
-use crate::config::TOKEN;
 use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::sync::Mutex;
 
-const URI: &str = "https://api.github.com/graphql";
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RateLimit {
+    cost: usize,
+    remaining: usize,
+    reset_at: String,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    data: Option<EnvelopeData>,
+    #[serde(default)]
+    errors: Vec<GraphQLError>,
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GraphQLError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnvelopeData {
+    rate_limit: Option<RateLimit>,
+}
+
+/// Marker error for a query that was printed instead of sent
+#[derive(Debug, thiserror::Error)]
+#[error("dry run: GraphQL query not sent")]
+pub struct DryRun;
+
+static RATE_LIMIT: Mutex<Option<RateLimit>> = Mutex::new(None);
+
+fn check_budget() -> surf::Result<()> {
+    let budget = match crate::config::BUDGET.get() {
+        Some(&budget) => budget,
+        None => return Ok(()),
+    };
+    match &*RATE_LIMIT.lock().unwrap() {
+        Some(rl) if rl.remaining < budget => Err(surf::Error::from_str(
+            surf::StatusCode::TooManyRequests,
+            format!(
+                "GraphQL budget exhausted: {} points remaining (below {}, last query cost {}), resets at {}",
+                rl.remaining, budget, rl.cost, rl.reset_at
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
 
 pub async fn query<T: DeserializeOwned>(q: &serde_json::Value) -> surf::Result<T> {
-    let mut res = surf::post(URI)
-        .header("Authorization", format!("bearer {}", *TOKEN))
-        .header("Accept", "application/vnd.github.merge-info-preview+json")
-        .body(q.to_string())
-        .await?;
-    res.body_json::<T>().await
+    check_budget()?;
+    if crate::config::dry_run() {
+        eprintln!(
+            "dry-run: POST {} operation={} variables={}",
+            crate::config::graphql_uri(),
+            operation_name(q),
+            q["variables"]
+        );
+        return Err(surf::Error::new(surf::StatusCode::Ok, DryRun));
+    }
+    let mut res = match crate::mock::response(operation_name(q)) {
+        Some(res) => res?,
+        None => {
+            let mut req = crate::client::CLIENT
+                .post(crate::config::graphql_uri())
+                .body(q.to_string());
+            if wants_merge_info(q) {
+                req = req.header("Accept", "application/vnd.github.merge-info-preview+json");
+            }
+            crate::client::send(operation_name(q), req).await?
+        }
+    };
+    let status = res.status();
+    let v = res.body_json::<serde_json::Value>().await?;
+    let envelope = serde_json::from_value::<Envelope>(v.clone()).ok();
+    if let Some(rl) = envelope
+        .as_ref()
+        .and_then(|e| e.data.as_ref())
+        .and_then(|d| d.rate_limit.clone())
+    {
+        *RATE_LIMIT.lock().unwrap() = Some(rl);
+    }
+    match serde_json::from_value(v) {
+        Ok(t) => Ok(t),
+        Err(e) => match envelope.and_then(error_message) {
+            Some(msg) => {
+                let status = if status.is_success() {
+                    surf::StatusCode::UnprocessableEntity
+                } else {
+                    status
+                };
+                Err(surf::Error::from_str(status, msg))
+            }
+            None => Err(e.into()),
+        },
+    }
+}
+
+/// Whether the query selects `mergeStateStatus`, which needs the merge info preview,
+/// without turning it off through `mergeInfo`
+fn wants_merge_info(q: &serde_json::Value) -> bool {
+    let selects = q["query"]
+        .as_str()
+        .is_some_and(|query| query.contains("mergeStateStatus"));
+    selects && q["variables"]["mergeInfo"].as_bool() != Some(false)
+}
+
+/// Name of the query or mutation operation, used to pick per-operation mock fixtures
+fn operation_name(q: &serde_json::Value) -> &str {
+    q["query"]
+        .as_str()
+        .map(str::trim_start)
+        .and_then(|query| {
+            query
+                .strip_prefix("query")
+                .or_else(|| query.strip_prefix("mutation"))
+        })
+        .and_then(|rest| {
+            rest.trim_start()
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()
+        })
+        .filter(|name| !name.is_empty())
+        .unwrap_or("query")
+}
+
+/// Summarizes the errors of a response whose `data` could not be used.
+fn error_message(envelope: Envelope) -> Option<String> {
+    if !envelope.errors.is_empty() {
+        let msgs: Vec<String> = envelope.errors.into_iter().map(|e| e.message).collect();
+        Some(msgs.join("; "))
+    } else if envelope.data.is_none() {
+        envelope.message
+    } else {
+        None
+    }
 }
@@ -1,10 +1,20 @@
 use clap::Parser;
+use colored::Colorize;
 use config::Format;
 use read_input::prelude::*;
+use std::io::IsTerminal;
 
+#[macro_use]
+mod output;
+
+mod app;
+mod audit;
+mod client;
 mod cmd;
 mod config;
+mod error;
 mod graphql;
+mod mock;
 mod rest;
 
 #[derive(Parser)]
@@ -13,69 +23,275 @@ struct Opt {
     command: Command,
     #[clap(short = 'f', default_value = "text")]
     format: Format,
+    /// Host of the GitHub instance to use, e.g. a GitHub Enterprise Server (default: GH_HOST or github.com)
+    #[clap(long = "hostname")]
+    hostname: Option<String>,
+    /// Read and write the config file at this path instead of the XDG location
+    #[clap(long = "config")]
+    config: Option<std::path::PathBuf>,
+    /// Write results to the given file instead of stdout
+    #[clap(long = "output")]
+    output: Option<std::path::PathBuf>,
+    /// Append a JSON line for each approve or merge to this file
+    #[clap(long = "audit-log")]
+    audit_log: Option<std::path::PathBuf>,
+    /// Print the API requests instead of sending them
+    #[clap(long = "dry-run")]
+    dry_run: bool,
+    /// Stop issuing GraphQL queries once the remaining rate limit drops below this
+    #[clap(long = "budget")]
+    budget: Option<usize>,
+    /// Print only the number of items found by prs, issues or notifications
+    #[clap(long = "count")]
+    count: bool,
+    /// Exit with status 1 when prs, issues or notifications found nothing
+    #[clap(long = "fail-on-empty")]
+    fail_on_empty: bool,
+    /// Keep only these comma-separated keys of each item in the JSON output
+    #[clap(long = "fields", value_delimiter = ',')]
+    fields: Vec<String>,
+    /// Never pipe long text output through PAGER
+    #[clap(long = "no-pager")]
+    no_pager: bool,
+    /// Make titles clickable OSC 8 hyperlinks (default: when the terminal is known to support them)
+    #[clap(long = "hyperlinks")]
+    hyperlinks: bool,
+    /// Report each page fetched by paginated REST requests on stderr
+    #[clap(short = 'v', long = "verbose")]
+    verbose: bool,
+    /// Print the duration of each API request and the total to stderr
+    #[clap(long = "timing")]
+    timing: bool,
+    /// Maximum number of API requests in flight at once
+    #[clap(long = "concurrency", default_value = "4", value_parser = clap::value_parser!(u16).range(1..))]
+    concurrency: u16,
+    /// Show dates in this timezone: utc, local or an offset like +09:00 (default: local if TZ is set)
+    #[clap(long = "timezone", value_parser = config::parse_timezone, allow_hyphen_values = true)]
+    timezone: Option<time::UtcOffset>,
+    /// Draw state indicators as emoji even when output is not a terminal
+    #[clap(long = "emoji", conflicts_with = "no_emoji")]
+    emoji: bool,
+    /// Draw state indicators as ASCII tags like [clean] instead of emoji
+    #[clap(long = "no-emoji")]
+    no_emoji: bool,
 }
 
 #[derive(Debug, Parser)]
 #[clap(rename_all = "kebab-case")]
 enum Command {
     /// Show pullrequests of the repository or user
-    Prs { slug: Vec<String> },
+    Prs(cmd::prs::Args),
+    /// Explain the state indicators of the pullrequest list
+    Legend,
+    /// Show open pullrequests awaiting my review
+    ReviewRequests(cmd::reviewrequests::Args),
     /// Show issues of the repository or user
-    Issues { slug: Vec<String> },
+    Issues(cmd::issues::Args),
+    /// Show issues and pullrequests assigned to me
+    Assigned(cmd::issuesearch::Args),
+    /// Show issues and pullrequests mentioning me
+    Mentioned(cmd::issuesearch::Args),
+    /// Show an overview of the repository
+    Repo { slug: String },
+    /// Show recent releases of the repository
+    Releases(cmd::releases::Args),
     /// Show contriburions of the user
     #[clap(alias = "grass")]
-    Contributions { user: Option<String> },
+    Contributions(cmd::contributions::Args),
+    /// Show repositories starred by the user
+    #[clap(alias = "stars")]
+    Starred { user: Option<String> },
     /// Show notifications of the user
-    Notifications {
-        #[clap(long = "read")]
-        read: bool,
-    },
+    Notifications(cmd::notifications::Args),
+    /// Approve the pullrequest of the repository
+    Approve { slug: String, num: usize },
+    /// Merge the pullrequest of the repository
+    MergeOne(cmd::pullrequest::MergeArgs),
+    /// Show the changed files of the pullrequest
+    Diff(cmd::diff::Args),
     /// Track assignees of the issues or pullrequests
     TrackAssignees { slug: String, num: usize },
     /// Search repositories
     Search(cmd::search::Query),
+    /// Run a GraphQL query read from a file
+    Graphql(cmd::raw::GraphqlArgs),
+    /// Send a REST API request and print the response
+    Rest(cmd::raw::RestArgs),
+    /// Print the JSON Schema of a command's JSON output
+    Schema { what: cmd::schema::Target },
+    /// Show the scopes granted to the token
+    Scopes,
     /// Login to GitHub
-    Login,
+    Login {
+        /// Read the token from stdin instead of prompting for it
+        #[clap(long = "token-stdin")]
+        token_stdin: bool,
+        /// Host the token belongs to (default: the --hostname host)
+        #[clap(long = "host")]
+        host: Option<String>,
+    },
     /// Logout to GitHub
-    Logout,
+    Logout {
+        /// Host to remove the token of (default: the --hostname host)
+        #[clap(long = "host", conflicts_with = "all")]
+        host: Option<String>,
+        /// Remove the tokens of all hosts
+        #[clap(long = "all")]
+        all: bool,
+    },
 }
 
-fn login() -> Result<(), std::io::Error> {
-    let token: String = input()
-        .msg("Input your GitHub Personal Access Token: ")
-        .get();
-    let conf = config::Config { token: Some(token) };
-    let s = toml::to_string(&conf).unwrap();
-    let path = config::CONFIG_PATH.clone();
-    let dir = path.parent().unwrap();
-    if !dir.exists() {
-        std::fs::create_dir_all(dir)?;
+fn login(token_stdin: bool, host: &str) -> error::Result<()> {
+    let token: String = if token_stdin || !std::io::stdin().is_terminal() {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        line.trim().to_owned()
+    } else {
+        input()
+            .msg("Input your GitHub Personal Access Token: ")
+            .get()
+    };
+    if token.is_empty() {
+        return Err(error::Error::InvalidInput("empty token".to_owned()));
     }
-    std::fs::write(&path, s)
+    let path = config::config_path()?;
+    let mut conf = config::Config::from_path(&path);
+    conf.set_token(host, token);
+    conf.save(&path)?;
+    eprintln!("logged in to {}", host);
+    Ok(())
 }
 
-fn logout() -> Result<(), std::io::Error> {
-    let path = config::CONFIG_PATH.clone();
-    if path.exists() {
-        std::fs::remove_file(&path)
+fn logout(host: &str, all: bool) -> error::Result<()> {
+    let path = config::config_path()?;
+    if all {
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        eprintln!("logged out of all hosts");
+        return Ok(());
+    }
+    let mut conf = config::Config::from_path(&path);
+    if !conf.remove_token(host) {
+        eprintln!("not logged in to {}", host);
+        return Ok(());
+    }
+    if conf.is_empty() {
+        std::fs::remove_file(&path)?;
     } else {
-        Ok(())
+        conf.save(&path)?;
     }
+    eprintln!("logged out of {}", host);
+    Ok(())
 }
 
 #[async_std::main]
-async fn main() -> surf::Result<()> {
-    let opt = Opt::parse();
+async fn main() {
+    let start = std::time::Instant::now();
+    let res = run(Opt::parse()).await;
+    // output held back for the pager is shown even when the command failed
+    let res = res.and(output::finish().map_err(error::Error::from));
+    if config::timing() {
+        eprintln!(
+            "timing: {:>8.3}s total, {} requests",
+            start.elapsed().as_secs_f64(),
+            client::SENT.load(std::sync::atomic::Ordering::Relaxed)
+        );
+    }
+    match res {
+        Ok(()) | Err(error::Error::DryRun) => {}
+        Err(error::Error::Empty) => std::process::exit(error::Error::Empty.exit_code()),
+        Err(e) => {
+            eprintln!("{}: {}", "error".red(), e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+async fn run(opt: Opt) -> error::Result<()> {
+    let host = opt
+        .hostname
+        .or_else(|| std::env::var("GH_HOST").ok().filter(|h| !h.is_empty()));
+    if let Some(host) = host {
+        config::HOST.set(host).expect("set host");
+    }
+    if let Some(path) = opt.config {
+        config::CONFIG_OVERRIDE.set(path).expect("set config path");
+    }
+    let page = !opt.no_pager && matches!(opt.format, Format::Text);
+    output::init(opt.output.as_deref(), page)?;
+    output::init_hyperlinks(opt.hyperlinks);
     config::FORMAT.set(opt.format).expect("set format");
+    config::DRY_RUN.set(opt.dry_run).expect("set dry run");
+    if let Some(path) = opt.audit_log {
+        audit::AUDIT_LOG.set(path).expect("set audit log");
+    }
+    config::TIMING.set(opt.timing).expect("set timing");
+    config::VERBOSE.set(opt.verbose).expect("set verbose");
+    config::CONCURRENCY
+        .set(opt.concurrency.into())
+        .expect("set concurrency");
+    let offset = match opt.timezone {
+        Some(offset) => Some(offset),
+        None if std::env::var_os("TZ").is_some() => time::UtcOffset::current_local_offset().ok(),
+        None => None,
+    };
+    if let Some(offset) = offset {
+        config::OFFSET.set(offset).expect("set offset");
+    }
+    let emoji = opt.emoji || (!opt.no_emoji && output::is_terminal());
+    config::EMOJI.set(emoji).expect("set emoji");
+    config::COUNT_ONLY.set(opt.count).expect("set count only");
+    config::FAIL_ON_EMPTY
+        .set(opt.fail_on_empty)
+        .expect("set fail on empty");
+    if !opt.fields.is_empty() {
+        config::FIELDS.set(opt.fields).expect("set fields");
+    }
+    // local commands must keep working while the app auth setup is broken
+    let local = matches!(
+        opt.command,
+        Command::Legend | Command::Schema { .. } | Command::Login { .. } | Command::Logout { .. }
+    );
+    if !local {
+        if let Some(token) = app::installation_token().await? {
+            config::INSTALLATION_TOKEN
+                .set(token)
+                .expect("set installation token");
+        }
+    }
+    if let Some(budget) = opt.budget {
+        config::BUDGET.set(budget).expect("set budget");
+    }
     match opt.command {
-        Command::Prs { slug } => cmd::prs::check(slug).await?,
-        Command::Issues { slug } => cmd::issues::check(slug).await?,
-        Command::Contributions { user } => cmd::contributions::check(user).await?,
-        Command::Notifications { read } => cmd::notifications::list(read).await?,
+        Command::Prs(args) => cmd::prs::check(&args).await?,
+        Command::Legend => cmd::prs::legend()?,
+        Command::ReviewRequests(args) => cmd::reviewrequests::check(&args).await?,
+        Command::Issues(args) => cmd::issues::check(&args).await?,
+        Command::Assigned(args) => {
+            cmd::issuesearch::search(cmd::issuesearch::Filter::Assigned, &args).await?
+        }
+        Command::Mentioned(args) => {
+            cmd::issuesearch::search(cmd::issuesearch::Filter::Mentioned, &args).await?
+        }
+        Command::Repo { slug } => cmd::repo::show(&slug).await?,
+        Command::Releases(args) => cmd::releases::list(&args).await?,
+        Command::Contributions(args) => cmd::contributions::check(&args).await?,
+        Command::Starred { user } => cmd::starred::list(user.as_deref()).await?,
+        Command::Notifications(args) => cmd::notifications::list(&args).await?,
+        Command::Approve { slug, num } => cmd::pullrequest::approve(&slug, num).await?,
+        Command::MergeOne(args) => cmd::pullrequest::merge(&args).await?,
+        Command::Diff(args) => cmd::diff::show(&args).await?,
         Command::TrackAssignees { slug, num } => cmd::trackassignees::track(&slug, num).await?,
         Command::Search(q) => cmd::search::search(&q).await?,
-        Command::Login => login()?,
-        Command::Logout => logout()?,
+        Command::Graphql(args) => cmd::raw::graphql(&args).await?,
+        Command::Rest(args) => cmd::raw::rest(&args).await?,
+        Command::Schema { what } => cmd::schema::print(&what)?,
+        Command::Scopes => cmd::scopes::check().await?,
+        Command::Login { token_stdin, host } => {
+            login(token_stdin, host.as_deref().unwrap_or(config::host()))?
+        }
+        Command::Logout { host, all } => logout(host.as_deref().unwrap_or(config::host()), all)?,
     };
     Ok(())
 }
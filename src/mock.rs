@@ -0,0 +1,47 @@
+use std::convert::TryFrom;
+
+/// Environment variable naming a JSON fixture, or a directory of per-operation
+/// fixtures, to answer API requests with
+const MOCK_FILE: &str = "GH_CHK_MOCK_FILE";
+
+/// Fixture response in place of a network round-trip, when mock mode is on.
+///
+/// When the mock path is a directory, the fixture is `<key>.json` inside it,
+/// where `key` is the GraphQL operation name or the REST path. A fixture of the
+/// form `{"$status": 403, "body": {...}}` answers with that status and body.
+pub fn response(key: &str) -> Option<surf::Result<surf::Response>> {
+    let mut path = std::path::PathBuf::from(std::env::var_os(MOCK_FILE)?);
+    if path.is_dir() {
+        path.push(key.trim_matches('/').replace('/', "_") + ".json");
+    }
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            return Some(Err(surf::Error::from_str(
+                surf::StatusCode::NotFound,
+                format!("mock {}: {}", path.display(), e),
+            )))
+        }
+    };
+    Some(from_fixture(text))
+}
+
+fn from_fixture(text: String) -> surf::Result<surf::Response> {
+    let v = serde_json::from_str::<serde_json::Value>(&text).ok();
+    let code = match v.as_ref().and_then(|v| v.get("$status")) {
+        Some(code) => code.as_u64().unwrap_or_default(),
+        None => return Ok(from_body(text)),
+    };
+    let status = surf::StatusCode::try_from(code as u16)?;
+    let mut res = surf::http::Response::new(status);
+    res.set_body(v.map(|v| v["body"].to_string()).unwrap_or_default());
+    res.set_content_type(surf::http::mime::JSON);
+    Ok(res.into())
+}
+
+pub fn from_body(body: String) -> surf::Response {
+    let mut res = surf::http::Response::new(surf::StatusCode::Ok);
+    res.set_body(body);
+    res.set_content_type(surf::http::mime::JSON);
+    res.into()
+}
@@ -0,0 +1,155 @@
+use std::fs::File;
+use std::io::{BufWriter, IsTerminal, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+static SINK: OnceLock<Mutex<Box<dyn Write + Send>>> = OnceLock::new();
+static TO_FILE: OnceLock<bool> = OnceLock::new();
+static HYPERLINKS: OnceLock<bool> = OnceLock::new();
+/// Output held back until `finish` to decide whether it needs a pager
+static PAGE_BUF: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::output::write_fmt(format_args!($($arg)*))
+    };
+}
+
+macro_rules! outln {
+    () => {
+        $crate::output::write_fmt(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::write_fmt(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+/// Directs output to the file at `path`, or to stdout through a pager when
+/// `page` is set and stdout is a terminal
+pub fn init(path: Option<&Path>, page: bool) -> std::io::Result<()> {
+    let sink: Box<dyn Write + Send> = match path {
+        Some(path) => {
+            if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+            colored::control::set_override(false);
+            Box::new(BufWriter::new(File::create(path)?))
+        }
+        None => Box::new(std::io::stdout()),
+    };
+    let _ = SINK.set(Mutex::new(sink));
+    let _ = TO_FILE.set(path.is_some());
+    if page && is_terminal() {
+        *PAGE_BUF.lock().unwrap() = Some(Vec::new());
+    }
+    Ok(())
+}
+
+/// Stops holding output back for the pager, e.g. for commands that redraw
+pub fn no_pager() -> std::io::Result<()> {
+    if let Some(buf) = PAGE_BUF.lock().unwrap().take() {
+        sink().lock().unwrap().write_all(&buf)?;
+    }
+    Ok(())
+}
+
+/// Flushes the output, through `PAGER` (`less -R` by default) when it is taller than the terminal
+pub fn finish() -> std::io::Result<()> {
+    let buf = match PAGE_BUF.lock().unwrap().take() {
+        Some(buf) => buf,
+        None => return flush(),
+    };
+    let lines = buf.iter().filter(|&&b| b == b'\n').count();
+    let tall = terminal_size().is_some_and(|(_, height)| lines >= height);
+    if !tall || !page(&buf) {
+        let mut sink = sink().lock().unwrap();
+        sink.write_all(&buf)?;
+        sink.flush()?;
+    }
+    Ok(())
+}
+
+/// Shows `buf` in the pager, returning false when no pager could be run
+fn page(buf: &[u8]) -> bool {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".to_owned());
+    let mut words = pager.split_whitespace();
+    let program = match words.next() {
+        Some("cat") | None => return false,
+        Some(program) => program,
+    };
+    let mut child = match std::process::Command::new(program)
+        .args(words)
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(_) => return false,
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pager may quit before reading everything
+        let _ = stdin.write_all(buf);
+    }
+    let _ = child.wait();
+    true
+}
+
+/// Whether output goes to an interactive terminal
+pub fn is_terminal() -> bool {
+    !TO_FILE.get().copied().unwrap_or_default() && std::io::stdout().is_terminal()
+}
+
+/// Columns and rows of the output terminal, if output goes to one
+pub fn terminal_size() -> Option<(usize, usize)> {
+    if !is_terminal() {
+        return None;
+    }
+    let (terminal_size::Width(w), terminal_size::Height(h)) = terminal_size::terminal_size()?;
+    Some((w as usize, h as usize))
+}
+
+/// `text` as an OSC 8 hyperlink to `url` when hyperlinks are enabled
+pub fn hyperlink(text: &str, url: &str) -> String {
+    if HYPERLINKS.get().copied().unwrap_or_default() {
+        format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+    } else {
+        text.to_owned()
+    }
+}
+
+/// Enables hyperlinks when forced or detected, but never for piped output or under `NO_COLOR`
+pub fn init_hyperlinks(force: bool) {
+    let supported = force || terminal_supports_hyperlinks();
+    let enabled = supported && is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let _ = HYPERLINKS.set(enabled);
+}
+
+fn terminal_supports_hyperlinks() -> bool {
+    let env = |name| std::env::var(name).unwrap_or_default();
+    matches!(
+        env("TERM_PROGRAM").as_str(),
+        "iTerm.app" | "WezTerm" | "vscode" | "ghostty"
+    ) || ["WT_SESSION", "KITTY_WINDOW_ID", "DOMTERM"]
+        .iter()
+        .any(|name| std::env::var_os(name).is_some())
+        || env("VTE_VERSION").parse::<u32>().is_ok_and(|v| v >= 5000)
+}
+
+fn sink() -> &'static Mutex<Box<dyn Write + Send>> {
+    SINK.get_or_init(|| Mutex::new(Box::new(std::io::stdout())))
+}
+
+pub fn write_fmt(args: std::fmt::Arguments) {
+    if let Some(buf) = PAGE_BUF.lock().unwrap().as_mut() {
+        buf.write_fmt(args).expect("failed buffering output");
+        return;
+    }
+    sink()
+        .lock()
+        .unwrap()
+        .write_fmt(args)
+        .expect("failed writing output");
+}
+
+pub fn flush() -> std::io::Result<()> {
+    sink().lock().unwrap().flush()
+}
@@ -1,8 +1,6 @@
-use crate::config::TOKEN;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 
-const BASE_URI: &str = "https://api.github.com/";
 pub type QueryMap = HashMap<String, String>;
 
 #[allow(dead_code)]
@@ -24,25 +22,157 @@ pub async fn get<T: DeserializeOwned>(
     page: usize,
     q: &QueryMap,
 ) -> surf::Result<Vec<T>> {
-    let uri = BASE_URI.to_owned() + path;
-    let mut res = get_page(&uri, page, q).await?;
+    let uri = crate::config::api_root() + path;
+    let tty = progress(path, page);
+    let res = get_page(&uri, page, q).await;
+    if tty {
+        eprint!("\r\x1b[K");
+    }
+    let mut res = res?;
+    if !res.status().is_success() {
+        return Err(status_error(path, &mut res).await);
+    }
+    res.body_json().await
+}
+
+/// Fetches a response that is a single object rather than a list of items
+pub async fn get_object<T: DeserializeOwned>(path: &str, q: &QueryMap) -> surf::Result<T> {
+    let uri = crate::config::api_root() + path;
+    let mut res = get_page(&uri, 1, q).await?;
+    if !res.status().is_success() {
+        return Err(status_error(path, &mut res).await);
+    }
     res.body_json().await
 }
 
+/// Reports the page being fetched with `--verbose`, or on a line of a terminal
+/// stderr that is cleared once the page arrives, returning whether it did the latter
+fn progress(path: &str, page: usize) -> bool {
+    use std::io::IsTerminal;
+    if crate::config::verbose() {
+        eprintln!("fetching {} page {}...", path, page);
+        false
+    } else if page > 1 && std::io::stderr().is_terminal() {
+        eprint!("\r\x1b[Kfetching {} page {}...", path, page);
+        true
+    } else {
+        false
+    }
+}
+
+/// Error for a failed response, carrying the `message` GitHub sent with it
+async fn status_error(path: &str, res: &mut surf::Response) -> surf::Error {
+    #[derive(serde::Deserialize)]
+    struct Message {
+        message: String,
+    }
+    let msg = match res.body_json::<Message>().await {
+        Ok(m) => format!("{} failed with {}: {}", path, res.status(), m.message),
+        Err(_) => format!("{} failed with {}", path, res.status()),
+    };
+    surf::Error::from_str(res.status(), msg)
+}
+
 pub async fn get_page(url: &str, page: usize, q: &QueryMap) -> surf::Result<surf::Response> {
     let mut query = HashMap::new();
     query.insert("page", page.to_string());
     query.insert("per_page", 100.to_string());
     query.extend(q.iter().map(|(k, v)| (k.as_str(), v.clone()))); // skipcq: RS-A1009
-    surf::get(url)
-        .header("Authorization", format!("token {}", *TOKEN))
-        .query(&query)?
-        .await
+    if crate::config::dry_run() {
+        eprintln!("dry-run: GET {} query={:?}", url, query);
+        return Ok(crate::mock::from_body("[]".to_owned()));
+    }
+    let root = crate::config::api_root();
+    let path = url.trim_start_matches(root.as_str());
+    match crate::mock::response(path) {
+        // a fixture stands for a single page
+        Some(_) if page > 1 => Ok(crate::mock::from_body("[]".to_owned())),
+        Some(res) => res,
+        None => {
+            let label = format!("GET {}", path);
+            crate::client::send(&label, crate::client::CLIENT.get(url).query(&query)?).await
+        }
+    }
 }
 
 pub async fn patch(path: &str) -> surf::Result<surf::Response> {
-    let uri = BASE_URI.to_owned() + path;
-    surf::patch(uri)
-        .header("Authorization", format!("token {}", *TOKEN))
-        .await
+    let uri = crate::config::api_root() + path;
+    if crate::config::dry_run() {
+        eprintln!("dry-run: PATCH {}", uri);
+        return Ok(crate::mock::from_body("{}".to_owned()));
+    }
+    let res = match crate::mock::response(path) {
+        Some(res) => res?,
+        None => {
+            crate::client::send(&format!("PATCH {}", path), crate::client::CLIENT.patch(uri))
+                .await?
+        }
+    };
+    match res.status() {
+        surf::StatusCode::Forbidden | surf::StatusCode::NotFound => Err(surf::Error::from_str(
+            res.status(),
+            format!(
+                "{} failed with {}; the token may lack a required scope (see `gh chk scopes`)",
+                path,
+                res.status()
+            ),
+        )),
+        _ => Ok(res),
+    }
+}
+
+/// Sends `fields` as the query of a GET request or the JSON body of any other method
+pub async fn request(
+    method: surf::http::Method,
+    path: &str,
+    fields: &serde_json::Map<String, serde_json::Value>,
+) -> surf::Result<surf::Response> {
+    let path = path.trim_start_matches('/');
+    let uri = crate::config::api_root() + path;
+    if crate::config::dry_run() {
+        eprintln!(
+            "dry-run: {} {} fields={}",
+            method,
+            uri,
+            serde_json::json!(fields)
+        );
+        return Ok(crate::mock::from_body("{}".to_owned()));
+    }
+    let mut res = match crate::mock::response(path) {
+        Some(res) => res?,
+        None => {
+            let mut req = crate::client::CLIENT.request(method, uri);
+            if method == surf::http::Method::Get {
+                req = req.query(fields)?;
+            } else {
+                req = req.body_json(fields)?;
+            }
+            crate::client::send(&format!("{} {}", method, path), req).await?
+        }
+    };
+    if !res.status().is_success() {
+        return Err(status_error(path, &mut res).await);
+    }
+    Ok(res)
+}
+
+pub async fn scopes() -> surf::Result<Vec<String>> {
+    let uri = crate::config::api_root() + "user";
+    if crate::config::dry_run() {
+        eprintln!("dry-run: GET {}", uri);
+        return Ok(Vec::new());
+    }
+    let res = match crate::mock::response("user") {
+        Some(res) => res?,
+        None => crate::client::send("GET user", crate::client::CLIENT.get(uri)).await?,
+    };
+    Ok(match res.header("X-OAuth-Scopes") {
+        Some(vs) => vs
+            .as_str()
+            .split(',')
+            .map(|s| s.trim().to_owned())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        None => Vec::new(),
+    })
 }
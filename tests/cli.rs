@@ -0,0 +1,221 @@
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+/// Runs gh-chk answering every request from the fixture `mock`
+fn run(mock: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_gh-chk"))
+        .env("GH_CHK_MOCK_FILE", fixture(mock))
+        .env("NO_COLOR", "1")
+        .env_remove("GH_CHK_INSTALLATION_TOKEN")
+        .env_remove("GH_CHK_APP_ID")
+        .args(args)
+        .output()
+        .expect("run gh-chk")
+}
+
+#[test]
+fn notifications_json() {
+    let out = run("notifications.json", &["-f", "json", "notifications"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let items: serde_json::Value = serde_json::from_slice(&out.stdout).expect("JSON output");
+    let items = items.as_array().expect("list of notifications");
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["id"], "101");
+    assert_eq!(items[0]["reason"], "review_requested");
+    assert_eq!(items[0]["subject"]["type"], "PullRequest");
+    assert_eq!(items[0]["subject"]["title"], "Fix the greeting");
+    assert_eq!(items[0]["updated_at"], "2024-05-01T12:00:00Z");
+    assert_eq!(items[1]["repository"]["full_name"], "octo/world");
+    assert_eq!(items[1]["subject"]["url"], serde_json::Value::Null);
+}
+
+#[test]
+fn graphql_errors_fail_with_message() {
+    let out = run("graphql_errors.json", &["repo", "octo/missing"]);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert_eq!(out.status.code(), Some(3), "{}", stderr);
+    assert!(
+        stderr.contains("Could not resolve to a Repository with the name 'octo/missing'."),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn rest_status_fails_with_message() {
+    let out = run("rest_forbidden.json", &["notifications"]);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert_eq!(out.status.code(), Some(3), "{}", stderr);
+    assert!(
+        stderr.contains("notifications failed with 403"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("Resource not accessible by integration"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn unexpected_response_fails_to_parse() {
+    for args in [&["notifications"][..], &["repo", "octo/hello"][..]] {
+        let out = run("unexpected_shape.json", args);
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert_eq!(out.status.code(), Some(4), "{:?}: {}", args, stderr);
+        assert!(stderr.contains("failed to parse"), "{:?}: {}", args, stderr);
+    }
+}
+
+#[test]
+fn prs_json_is_one_document_per_slug() {
+    let out = run("repo_prs.json", &["-f", "json", "prs", "octo/hello"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let res: serde_json::Value = serde_json::from_slice(&out.stdout).expect("JSON output");
+    let prs = &res["data"]["repositoryOwner"]["repository"]["pullRequests"]["nodes"];
+    assert_eq!(prs.as_array().map(Vec::len), Some(2));
+}
+
+#[test]
+fn dry_run_assigned_succeeds() {
+    let out = run("notifications.json", &["--dry-run", "assigned"]);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert_eq!(out.status.code(), Some(0), "{}", stderr);
+    assert!(stderr.contains("dry-run: GET"), "{}", stderr);
+    assert!(stderr.contains("assignee:<viewer>"), "{}", stderr);
+}
+
+#[test]
+fn issue_search_status_fails_with_message() {
+    let out = run("search_forbidden", &["assigned"]);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert_eq!(out.status.code(), Some(3), "{}", stderr);
+    assert!(
+        stderr.contains("search/issues failed with 422: Validation Failed"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn local_commands_skip_app_auth() {
+    for args in [&["legend"][..], &["schema", "prs"][..]] {
+        let out = Command::new(env!("CARGO_BIN_EXE_gh-chk"))
+            .env("GH_CHK_MOCK_FILE", fixture("search_forbidden"))
+            .env("GH_CHK_APP_ID", "1")
+            .env("GH_CHK_INSTALLATION_ID", "2")
+            .env("GH_CHK_APP_PRIVATE_KEY", fixture("missing.pem"))
+            .env_remove("GH_CHK_INSTALLATION_TOKEN")
+            .args(args)
+            .output()
+            .expect("run gh-chk");
+        assert!(
+            out.status.success(),
+            "{:?}: {}",
+            args,
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+}
+
+#[test]
+fn fixture_directory_answers_each_operation() {
+    let out = run("operations", &["prs"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    // the login comes from Viewer.json, the PRs of that owner from OwnerPrs.json
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("octocat"));
+    assert_eq!(lines.next().map(str::trim_end), Some("hello (1)"));
+    assert!(stdout.contains("Greet in French"), "{}", stdout);
+}
+
+/// Checks `value` against the parts of JSON Schema the generated schemas use:
+/// references, alternatives, object properties and array items
+fn conforms(value: &Value, schema: &Value, root: &Value) -> bool {
+    if let Some(accepts) = schema.as_bool() {
+        return accepts;
+    }
+    if let Some(r) = schema["$ref"].as_str() {
+        let name = r.trim_start_matches("#/$defs/");
+        return conforms(value, &root["$defs"][name], root);
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(alts) = schema[key].as_array() {
+            return alts.iter().any(|s| conforms(value, s, root));
+        }
+    }
+    match value {
+        Value::Object(map) => {
+            let required = schema["required"].as_array().cloned().unwrap_or_default();
+            let props = &schema["properties"];
+            let extra = &schema["additionalProperties"];
+            required
+                .iter()
+                .all(|k| k.as_str().map_or(false, |k| map.contains_key(k)))
+                && map.iter().all(|(k, v)| match props.get(k) {
+                    Some(s) => conforms(v, s, root),
+                    None => extra.is_object() && conforms(v, extra, root),
+                })
+        }
+        Value::Array(items) => items.iter().all(|v| conforms(v, &schema["items"], root)),
+        _ => true,
+    }
+}
+
+#[test]
+fn json_output_matches_schema() {
+    for (mock, target, args) in [
+        ("repo_prs.json", "prs", &["prs", "octo/hello"][..]),
+        (
+            "notifications.json",
+            "notifications",
+            &["notifications"][..],
+        ),
+    ] {
+        let schema = run(mock, &["schema", target]);
+        let schema: Value = serde_json::from_slice(&schema.stdout).expect("JSON schema");
+        let out = run(mock, &[&["-f", "json"][..], args].concat());
+        let res: Value = serde_json::from_slice(&out.stdout).expect("JSON output");
+        assert!(
+            conforms(&res, &schema, &schema),
+            "{} output: {}",
+            target,
+            res
+        );
+    }
+}
+
+#[test]
+fn logout_without_home_is_a_config_error() {
+    let out = Command::new(env!("CARGO_BIN_EXE_gh-chk"))
+        .env_remove("HOME")
+        .env_remove("XDG_CONFIG_HOME")
+        .args(["logout"])
+        .output()
+        .expect("run gh-chk");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert_eq!(out.status.code(), Some(3), "{}", stderr);
+    assert!(stderr.contains("config error"), "{}", stderr);
+}